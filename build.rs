@@ -0,0 +1,35 @@
+fn main() {
+    #[cfg(feature = "capi")]
+    generate_header();
+
+    #[cfg(feature = "node")]
+    napi_build::setup();
+}
+
+/// Writes `khqr.h` to `OUT_DIR`, and also into `KHQR_HEADER_DIR` when it is set, so that
+/// ordinary builds never touch the source tree. Refresh the checked-in header with
+/// `KHQR_HEADER_DIR=include cargo build --features capi`.
+#[cfg(feature = "capi")]
+fn generate_header() {
+    let crate_dir =
+        std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is set by cargo");
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir))
+        .expect("cbindgen.toml is valid");
+
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-changed=src/capi.rs");
+    println!("cargo:rerun-if-env-changed=KHQR_HEADER_DIR");
+
+    let bindings = cbindgen::Builder::new()
+        .with_crate(&crate_dir)
+        .with_config(config)
+        .generate()
+        .expect("C header generation failed");
+
+    bindings.write_to_file(format!("{}/khqr.h", out_dir));
+    if let Ok(header_dir) = std::env::var("KHQR_HEADER_DIR") {
+        let header_dir = std::path::Path::new(&crate_dir).join(header_dir);
+        bindings.write_to_file(header_dir.join("khqr.h"));
+    }
+}
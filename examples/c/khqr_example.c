@@ -0,0 +1,50 @@
+#include <stdio.h>
+#include <string.h>
+
+#include "khqr.h"
+
+int main(void) {
+    KhqrRequest request;
+    memset(&request, 0, sizeof request);
+    request.bakong_account_identifier = "abc@dev";
+    request.merchant_name = "Example";
+    request.merchant_city = "Phnom Penh";
+    request.currency = KHQR_CURRENCY_KHR;
+    request.amount = "1000";
+    request.bill_number = "INV-1";
+
+    char *qr = NULL;
+    if (khqr_generate(&request, &qr) != KHQR_STATUS_OK) {
+        fprintf(stderr, "generate failed: %s\n", khqr_last_error());
+        return 1;
+    }
+    printf("payload: %s\n", qr);
+
+    if (khqr_validate(qr) != KHQR_STATUS_OK) {
+        fprintf(stderr, "validate failed: %s\n", khqr_last_error());
+        khqr_string_free(qr);
+        return 1;
+    }
+
+    KhqrPayload *payload = NULL;
+    if (khqr_decode(qr, &payload) != KHQR_STATUS_OK) {
+        fprintf(stderr, "decode failed: %s\n", khqr_last_error());
+        khqr_string_free(qr);
+        return 1;
+    }
+    const char *merchant = NULL, *amount = NULL, *bill = NULL;
+    khqr_payload_field(payload, KHQR_FIELD_MERCHANT_NAME, &merchant);
+    khqr_payload_field(payload, KHQR_FIELD_AMOUNT, &amount);
+    khqr_payload_field(payload, KHQR_FIELD_BILL_NUMBER, &bill);
+    printf("merchant: %s\n", merchant);
+    printf("amount:   %s\n", amount);
+    printf("bill:     %s\n", bill);
+
+    uint16_t crc = 0;
+    khqr_crc("123456789", &crc);
+    printf("crc:      %04X\n", crc);
+
+    khqr_free(payload);
+    khqr_string_free(qr);
+    return 0;
+}
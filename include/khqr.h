@@ -0,0 +1,133 @@
+#ifndef KHQR_H
+#define KHQR_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef enum KhqrStatus {
+  KHQR_STATUS_OK = 0,
+  KHQR_STATUS_NULL_POINTER = -1,
+  KHQR_STATUS_INVALID_UTF8 = -2,
+  KHQR_STATUS_INVALID_INPUT = -3,
+  KHQR_STATUS_INVALID_DATA = -4,
+  KHQR_STATUS_OTHER = -5,
+} KhqrStatus;
+
+typedef enum KhqrCurrency {
+  KHQR_CURRENCY_KHR = 116,
+  KHQR_CURRENCY_USD = 840,
+} KhqrCurrency;
+
+typedef enum KhqrField {
+  KHQR_FIELD_BAKONG_ACCOUNT_IDENTIFIER,
+  KHQR_FIELD_MERCHANT_IDENTIFIER,
+  KHQR_FIELD_ACQUIRING_BANK,
+  KHQR_FIELD_MERCHANT_NAME,
+  KHQR_FIELD_MERCHANT_CITY,
+  KHQR_FIELD_MERCHANT_CATEGORY_CODE,
+  KHQR_FIELD_AMOUNT,
+  KHQR_FIELD_BILL_NUMBER,
+  KHQR_FIELD_STORE_LABEL,
+  KHQR_FIELD_TERMINAL_LABEL,
+  KHQR_FIELD_MOBILE_NUMBER,
+  KHQR_FIELD_PURPOSE_OF_TRANSACTION,
+  KHQR_FIELD_COUNTRY_CODE,
+} KhqrField;
+
+// Decoded payload returned by `khqr_decode`, released with `khqr_free`.
+typedef struct KhqrPayload KhqrPayload;
+
+// Input for `khqr_generate`. Every string is optional unless noted and must be NUL-terminated.
+typedef struct KhqrRequest {
+  // Required.
+  const char *bakong_account_identifier;
+  // Set to produce a merchant (tag 30) QR; NULL produces an individual (tag 29) QR.
+  const char *merchant_identifier;
+  const char *acquiring_bank;
+  // Required.
+  const char *merchant_name;
+  const char *merchant_city;
+  const char *merchant_category_code;
+  // A `KhqrCurrency`; any other value returns `KHQR_STATUS_INVALID_INPUT`.
+  int currency;
+  // NULL produces a static QR.
+  const char *amount;
+  // Expiry in Unix milliseconds for dynamic QRs, or 0 for none.
+  int64_t expiration_timestamp_ms;
+  const char *bill_number;
+  const char *store_label;
+  const char *terminal_label;
+  const char *mobile_number;
+  const char *purpose_of_transaction;
+} KhqrRequest;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Generates a payload from `request` into `*out`, which must be released with `khqr_string_free`.
+//
+// # Safety
+// `request` must point to a valid `KhqrRequest` and `out` to writable storage for a pointer.
+enum KhqrStatus khqr_generate(const struct KhqrRequest *request, char **out);
+
+// Decodes `qr` into `*out`, which must be released with `khqr_free`. Payloads that are not
+// ASCII are rejected with `KHQR_STATUS_INVALID_INPUT`.
+//
+// # Safety
+// `qr` must be a NUL-terminated string and `out` must be writable storage for a pointer.
+enum KhqrStatus khqr_decode(const char *qr, struct KhqrPayload **out);
+
+// Returns `KHQR_STATUS_OK` when `qr` decodes and its CRC matches, otherwise the first error.
+// Warnings, such as tags out of canonical order, do not fail validation.
+//
+// # Safety
+// `qr` must be a NUL-terminated string.
+enum KhqrStatus khqr_validate(const char *qr);
+
+// Computes the CRC-16/CCITT-FALSE checksum used by tag 63.
+//
+// # Safety
+// `data` must be a NUL-terminated string and `out` must be writable.
+enum KhqrStatus khqr_crc(const char *data, uint16_t *out);
+
+// Stores a field of a decoded payload in `*out`, or NULL when absent. The string is valid until
+// `khqr_free`. `field` is a `KhqrField`; any other value returns `KHQR_STATUS_INVALID_INPUT`.
+//
+// # Safety
+// `payload` must be NULL or a pointer returned by `khqr_decode`, and `out` must be writable.
+enum KhqrStatus khqr_payload_field(const struct KhqrPayload *payload, int field, const char **out);
+
+// # Safety
+// `payload` must be NULL or a pointer returned by `khqr_decode`.
+bool khqr_payload_is_static(const struct KhqrPayload *payload);
+
+// # Safety
+// `payload` must be a pointer returned by `khqr_decode`.
+enum KhqrCurrency khqr_payload_currency(const struct KhqrPayload *payload);
+
+// Returns the tag 99 expiry in Unix milliseconds, or 0 when absent.
+//
+// # Safety
+// `payload` must be NULL or a pointer returned by `khqr_decode`.
+int64_t khqr_payload_expiration_ms(const struct KhqrPayload *payload);
+
+// Returns the message for the last failed call on this thread, or NULL.
+const char *khqr_last_error(void);
+
+// # Safety
+// `payload` must be NULL or a pointer returned by `khqr_decode` that was not freed yet.
+void khqr_free(struct KhqrPayload *payload);
+
+// # Safety
+// `value` must be NULL or a string returned by `khqr_generate` that was not freed yet.
+void khqr_string_free(char *value);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* KHQR_H */
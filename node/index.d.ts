@@ -0,0 +1,19 @@
+// Generated from the #[napi] functions in src/node.rs by tests/node_typings.rs.
+import type { Bakong, Finding, KhqrResult } from "./types";
+
+export * from "./types";
+
+/** Encodes `bakong` into a KHQR payload. */
+export declare function generate(bakong: Bakong): KhqrResult<string>;
+
+/** Decodes a KHQR payload, checking its CRC. */
+export declare function decode(qr: string): KhqrResult<Bakong>;
+
+/**
+ * Lists every problem in a payload; a payload that fails to decode yields error findings
+ * rather than an exception.
+ */
+export declare function validate(qr: string): Finding[];
+
+/** Renders a payload as an SVG document at least `size` pixels wide. */
+export declare function renderSvg(qr: string, size: number): KhqrResult<string>;
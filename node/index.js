@@ -0,0 +1 @@
+module.exports = require("./khqr_sdk.node");
@@ -0,0 +1,41 @@
+// Exercises the built addon; see the `node` feature in the README for how to build khqr_sdk.node.
+const assert = require("node:assert/strict");
+const test = require("node:test");
+const khqr = require(".");
+
+const STATIC_KHR = "00020101021129110007abc@dev53031165802KH5902Ex6010Phnom Penh63042C8E";
+
+test("generate and decode round-trip", () => {
+  const decoded = khqr.decode(STATIC_KHR);
+  assert.equal(decoded.ok, true);
+  assert.equal(decoded.value.merchant_name, "Ex");
+  assert.deepEqual(decoded.value.qr_type, { Static: "KHR" });
+
+  assert.deepEqual(khqr.generate(decoded.value), { ok: true, value: STATIC_KHR });
+});
+
+test("failures come back as error results", () => {
+  const decoded = khqr.decode(STATIC_KHR.replace("2C8E", "0000"));
+  assert.deepEqual(decoded, {
+    ok: false,
+    error: { kind: "InvalidData", message: "CRC is missing or does not match the payload" },
+  });
+
+  const generated = khqr.generate({});
+  assert.equal(generated.ok, false);
+  assert.equal(generated.error.kind, "InvalidInput");
+});
+
+test("validate reports undecodable payloads as findings", () => {
+  assert.deepEqual(khqr.validate(STATIC_KHR), []);
+
+  const findings = khqr.validate("not a payload");
+  assert.ok(findings.length > 0);
+  assert.ok(findings.every((finding) => finding.severity === "Error"));
+});
+
+test("renderSvg returns an SVG document", () => {
+  const svg = khqr.renderSvg(STATIC_KHR, 128);
+  assert.equal(svg.ok, true);
+  assert.match(svg.value, /^<\?xml|^<svg/);
+});
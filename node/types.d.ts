@@ -0,0 +1,165 @@
+/** Shapes of the JSON values the addon exchanges; the function typings in `index.d.ts` are generated. */
+
+export type TransactionCurrency = "KHR" | "USD";
+
+export type TransactionAmount = { KHR: number } | { USD: number };
+
+export interface AdditionalDataField {
+  /** RFC 3339 timestamp. */
+  creation_timestamp: string;
+  /** RFC 3339 timestamp. */
+  expiration_timestamp: string;
+}
+
+export type PointOfInitialMethod =
+  | { Static: TransactionCurrency }
+  | {
+      Dynamic: {
+        additional_data_field?: AdditionalDataField | null;
+        amount: TransactionAmount;
+      };
+    };
+
+export interface IndivualInformation {
+  bakong_account_identifier: string;
+  individual_account_information?: string | null;
+  acquiring_bank?: string | null;
+}
+
+export interface MerchantInformation {
+  bakong_account_identifier: string;
+  merchant_identifier: string;
+  acquiring_bank: string;
+}
+
+export type MerchantType =
+  | { Indivual: IndivualInformation }
+  | { Merchant: MerchantInformation };
+
+export type MerchantCity =
+  | "Battambang"
+  | "BanteayMeanChey"
+  | "KampongCham"
+  | "KampongChhnang"
+  | "KampongSpeu"
+  | "KampongThom"
+  | "Kandal"
+  | "Kep"
+  | "KohKong"
+  | "Kratie"
+  | "Mondolkiri"
+  | "OddorMeanchey"
+  | "Pailin"
+  | "PoiPet"
+  | "PhnomPenh"
+  | "PreahVihear"
+  | "PreyVeng"
+  | "Pursat"
+  | "Ratanakiri"
+  | "SiemReap"
+  | "SihanoukVille"
+  | "SteungTreng"
+  | "SvayRieng"
+  | "Takeo"
+  | "TboungKhmum";
+
+export type CountryCode =
+  | "KH"
+  | "TH"
+  | "VN"
+  | "LA"
+  | "MY"
+  | "SG"
+  | "ID"
+  | "PH"
+  | "MM"
+  | "BN"
+  | "CN"
+  | "HK"
+  | "JP"
+  | "KR";
+
+export interface AdditionalDataTemplate {
+  bill_number?: string | null;
+  store_label?: string | null;
+  terminal_label?: string | null;
+  mobile_number?: string | null;
+  purpose_of_transaction?: string | null;
+}
+
+export interface MerchantInformationLanguageTemplate {
+  language_preference: string;
+  merchant_name_alternate_language: string;
+  merchant_city_alternate_language: string;
+}
+
+export interface NetworkMerchantAccount {
+  /** 2-14 or 16-25. */
+  tag: number;
+  merchant_account: string;
+}
+
+/** Another scheme's template in tags 26–28 or 31–51, kept verbatim. */
+export interface MerchantAccountTemplate {
+  tag: number;
+  value: string;
+}
+
+/** A plain string is a value that is not an 8-digit IIN followed by a merchant ID. */
+export type UnionPayMerchant =
+  | { acquirer_iin: string; merchant_id: string }
+  | string;
+
+export type TipOrConvenienceIndicator = "PromptForTip" | "FixedFee" | "PercentageFee";
+
+export interface TipOrConvenienceFee {
+  indicator: TipOrConvenienceIndicator;
+  /** Required for "FixedFee", in the QR's currency. */
+  fixed_fee?: TransactionAmount | null;
+  /** Required for "PercentageFee", 0.01 to 99.99. */
+  percentage_fee?: number | null;
+}
+
+/** Proprietary sub-tag 01–99 of an unreserved template. */
+export interface UnreservedField {
+  tag: number;
+  value: string;
+}
+
+/** Tags 80–98: a GUID (sub-tag 00, up to 32 characters) plus proprietary sub-tags. */
+export interface UnreservedTemplate {
+  tag: number;
+  guid: string;
+  fields?: UnreservedField[];
+}
+
+export interface Bakong {
+  qr_type: PointOfInitialMethod;
+  merchant_type: MerchantType;
+  merchant_category_code?: string | null;
+  tip_or_convenience_fee?: TipOrConvenienceFee | null;
+  merchant_name: string;
+  merchant_city?: MerchantCity | null;
+  /** Tag 61; a Cambodian code is 6 digits starting with the province number. */
+  postal_code?: string | null;
+  country_code?: CountryCode | null;
+  additional_data_template?: AdditionalDataTemplate | null;
+  unionpay_merchant?: UnionPayMerchant | null;
+  network_merchant_accounts?: NetworkMerchantAccount[];
+  merchant_account_templates?: MerchantAccountTemplate[];
+  merchant_information_language_template?: MerchantInformationLanguageTemplate | null;
+  unreserved_templates?: UnreservedTemplate[];
+}
+
+export interface Finding {
+  severity: "Error" | "Warning";
+  path: string | null;
+  message: string;
+}
+
+export interface KhqrError {
+  kind: "InvalidInput" | "InvalidData" | "Other";
+  message: string;
+}
+
+export type KhqrResult<T> = { ok: true; value: T } | { ok: false; error: KhqrError };
@@ -0,0 +1,36 @@
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: khqr-diff [--crc] <old-payload> <new-payload>";
+
+fn main() -> ExitCode {
+    let mut include_crc = false;
+    let mut payloads = Vec::new();
+
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--crc" => include_crc = true,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            _ => payloads.push(arg),
+        }
+    }
+
+    let [old, new] = payloads.as_slice() else {
+        eprintln!("{}", USAGE);
+        return ExitCode::from(2);
+    };
+
+    match khqr_sdk::diff(old, new, include_crc) {
+        Ok(diff) if diff.is_empty() => ExitCode::SUCCESS,
+        Ok(diff) => {
+            print!("{}", diff);
+            ExitCode::from(1)
+        }
+        Err(e) => {
+            eprintln!("khqr-diff: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
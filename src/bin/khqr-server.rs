@@ -0,0 +1,49 @@
+use khqr_sdk::MerchantConfig;
+use std::io::{Error, ErrorKind};
+
+const USAGE: &str = "usage: khqr-server --config <merchants.toml|merchants.json> [--bind <addr>]
+
+Both options can also be set through KHQR_CONFIG and KHQR_BIND.";
+
+#[tokio::main]
+async fn main() {
+    if let Err(e) = run().await {
+        eprintln!("khqr-server: {}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn run() -> Result<(), Error> {
+    let mut config = std::env::var("KHQR_CONFIG").ok();
+    let mut bind = std::env::var("KHQR_BIND").unwrap_or_else(|_| "127.0.0.1:8080".to_string());
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--config" => config = args.next(),
+            "--bind" => bind = args.next().unwrap_or(bind),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("unexpected argument '{}'\n{}", arg, USAGE),
+                ));
+            }
+        }
+    }
+
+    let config = config.ok_or_else(|| Error::new(ErrorKind::InvalidInput, USAGE))?;
+    let config = MerchantConfig::load(config)?;
+
+    let listener = tokio::net::TcpListener::bind(&bind).await?;
+    eprintln!("khqr-server: listening on {}", listener.local_addr()?);
+
+    axum::serve(listener, khqr_sdk::router(config))
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await
+}
@@ -0,0 +1,510 @@
+use crate::error::{Error, ErrorKind};
+use crate::extension::Extensions;
+use crate::models::{
+    AdditionalDataField, AdditionalDataTemplate, Bakong, IndivualInformation, MerchantCity,
+    MerchantInformation, MerchantType, PointOfInitialMethod, TransactionAmount,
+    TransactionCurrency, crc16_ccitt,
+};
+use crate::validation::{self, Severity};
+use chrono::{TimeZone, Utc};
+use std::cell::RefCell;
+use std::ffi::{CStr, CString, c_char, c_int};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KhqrStatus {
+    Ok = 0,
+    NullPointer = -1,
+    InvalidUtf8 = -2,
+    InvalidInput = -3,
+    InvalidData = -4,
+    Other = -5,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KhqrCurrency {
+    Khr = 116,
+    Usd = 840,
+}
+
+/// Input for `khqr_generate`. Every string is optional unless noted and must be NUL-terminated.
+#[repr(C)]
+pub struct KhqrRequest {
+    /// Required.
+    pub bakong_account_identifier: *const c_char,
+    /// Set to produce a merchant (tag 30) QR; NULL produces an individual (tag 29) QR.
+    pub merchant_identifier: *const c_char,
+    pub acquiring_bank: *const c_char,
+    /// Required.
+    pub merchant_name: *const c_char,
+    pub merchant_city: *const c_char,
+    pub merchant_category_code: *const c_char,
+    /// A `KhqrCurrency`; any other value returns `KHQR_STATUS_INVALID_INPUT`.
+    pub currency: c_int,
+    /// NULL produces a static QR.
+    pub amount: *const c_char,
+    /// Expiry in Unix milliseconds for dynamic QRs, or 0 for none.
+    pub expiration_timestamp_ms: i64,
+    pub bill_number: *const c_char,
+    pub store_label: *const c_char,
+    pub terminal_label: *const c_char,
+    pub mobile_number: *const c_char,
+    pub purpose_of_transaction: *const c_char,
+}
+
+/// Decoded payload returned by `khqr_decode`, released with `khqr_free`.
+pub struct KhqrPayload {
+    bakong: Bakong,
+    fields: Vec<Option<CString>>,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KhqrField {
+    BakongAccountIdentifier,
+    MerchantIdentifier,
+    AcquiringBank,
+    MerchantName,
+    MerchantCity,
+    MerchantCategoryCode,
+    Amount,
+    BillNumber,
+    StoreLabel,
+    TerminalLabel,
+    MobileNumber,
+    PurposeOfTransaction,
+    CountryCode,
+}
+
+const FIELDS: [KhqrField; 13] = [
+    KhqrField::BakongAccountIdentifier,
+    KhqrField::MerchantIdentifier,
+    KhqrField::AcquiringBank,
+    KhqrField::MerchantName,
+    KhqrField::MerchantCity,
+    KhqrField::MerchantCategoryCode,
+    KhqrField::Amount,
+    KhqrField::BillNumber,
+    KhqrField::StoreLabel,
+    KhqrField::TerminalLabel,
+    KhqrField::MobileNumber,
+    KhqrField::PurposeOfTransaction,
+    KhqrField::CountryCode,
+];
+
+impl KhqrPayload {
+    fn new(bakong: Bakong) -> Self {
+        let fields = FIELDS
+            .iter()
+            .map(|field| field_value(&bakong, *field).and_then(|value| CString::new(value).ok()))
+            .collect();
+
+        Self { bakong, fields }
+    }
+}
+
+fn field_value(bakong: &Bakong, field: KhqrField) -> Option<String> {
+    let template = bakong.additional_data_template.as_ref();
+    match field {
+        KhqrField::BakongAccountIdentifier => Some(match &bakong.merchant_type {
+            MerchantType::Indivual(info) => info.bakong_account_identifier.clone(),
+            MerchantType::Merchant(info) => info.bakong_account_identifier.clone(),
+        }),
+        KhqrField::MerchantIdentifier => match &bakong.merchant_type {
+            MerchantType::Merchant(info) => Some(info.merchant_identifier.clone()),
+            MerchantType::Indivual(_) => None,
+        },
+        KhqrField::AcquiringBank => match &bakong.merchant_type {
+            MerchantType::Indivual(info) => info.acquiring_bank.clone(),
+            MerchantType::Merchant(info) => Some(info.acquiring_bank.clone()),
+        },
+        KhqrField::MerchantName => Some(bakong.merchant_name.clone()),
+        KhqrField::MerchantCity => bakong.merchant_city.as_ref().map(|c| c.city().to_string()),
+        KhqrField::MerchantCategoryCode => bakong.merchant_category_code.clone(),
+        KhqrField::Amount => match &bakong.qr_type {
+            PointOfInitialMethod::Dynamic { amount, .. } => Some(amount.to_string()),
+            PointOfInitialMethod::Static(_) => None,
+        },
+        KhqrField::BillNumber => template.and_then(|t| t.bill_number.clone()),
+        KhqrField::StoreLabel => template.and_then(|t| t.store_label.clone()),
+        KhqrField::TerminalLabel => template.and_then(|t| t.terminal_label.clone()),
+        KhqrField::MobileNumber => template.and_then(|t| t.mobile_number.clone()),
+        KhqrField::PurposeOfTransaction => template.and_then(|t| t.purpose_of_transaction.clone()),
+        KhqrField::CountryCode => bakong.country_code.as_ref().map(|c| c.code().to_string()),
+    }
+}
+
+fn fail(status: KhqrStatus, message: impl Into<Vec<u8>>) -> KhqrStatus {
+    let message = CString::new(message).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+    status
+}
+
+fn fail_with(error: Error) -> KhqrStatus {
+    let status = match error.kind() {
+        ErrorKind::InvalidInput => KhqrStatus::InvalidInput,
+        ErrorKind::InvalidData => KhqrStatus::InvalidData,
+        _ => KhqrStatus::Other,
+    };
+    fail(status, error.to_string())
+}
+
+/// Runs an entry point, turning a panic into `on_panic()` instead of unwinding into C.
+fn catch<T>(on_panic: impl FnOnce() -> T, f: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|_| on_panic())
+}
+
+fn catch_status(f: impl FnOnce() -> KhqrStatus) -> KhqrStatus {
+    catch(|| fail(KhqrStatus::Other, "Internal error"), f)
+}
+
+/// # Safety
+/// Same requirements as [`optional_str`].
+unsafe fn required_payload<'a>(qr: *const c_char) -> Result<&'a str, KhqrStatus> {
+    let qr = unsafe { required_str(qr, "qr") }?;
+    if !qr.is_ascii() {
+        return Err(fail(KhqrStatus::InvalidInput, "Payload must be ASCII"));
+    }
+    Ok(qr)
+}
+
+/// # Safety
+/// `value` must be NULL or point to a NUL-terminated string that outlives the returned slice.
+unsafe fn optional_str<'a>(value: *const c_char) -> Result<Option<&'a str>, KhqrStatus> {
+    if value.is_null() {
+        return Ok(None);
+    }
+    unsafe { CStr::from_ptr(value) }
+        .to_str()
+        .map(Some)
+        .map_err(|_| fail(KhqrStatus::InvalidUtf8, "String is not valid UTF-8"))
+}
+
+/// # Safety
+/// Same requirements as [`optional_str`].
+unsafe fn required_str<'a>(value: *const c_char, name: &str) -> Result<&'a str, KhqrStatus> {
+    unsafe { optional_str(value) }?
+        .ok_or_else(|| fail(KhqrStatus::NullPointer, format!("{} is required", name)))
+}
+
+unsafe fn build_request(request: &KhqrRequest) -> Result<Bakong, KhqrStatus> {
+    let owned = |value: *const c_char| -> Result<Option<String>, KhqrStatus> {
+        Ok(unsafe { optional_str(value) }?.map(str::to_string))
+    };
+
+    let bakong_account_identifier = unsafe {
+        required_str(
+            request.bakong_account_identifier,
+            "bakong_account_identifier",
+        )
+    }?
+    .to_string();
+    let merchant_type = match owned(request.merchant_identifier)? {
+        Some(merchant_identifier) => MerchantType::Merchant(MerchantInformation {
+            bakong_account_identifier,
+            merchant_identifier,
+            acquiring_bank: unsafe { required_str(request.acquiring_bank, "acquiring_bank") }?
+                .to_string(),
+        }),
+        None => MerchantType::Indivual(IndivualInformation {
+            bakong_account_identifier,
+            individual_account_information: None,
+            acquiring_bank: owned(request.acquiring_bank)?,
+        }),
+    };
+
+    // Read as an integer: a C caller can store any value, and only declared discriminants are
+    // valid for a Rust enum.
+    let currency = match request.currency {
+        currency if currency == KhqrCurrency::Khr as c_int => TransactionCurrency::KHR,
+        currency if currency == KhqrCurrency::Usd as c_int => TransactionCurrency::USD,
+        currency => {
+            return Err(fail(
+                KhqrStatus::InvalidInput,
+                format!("Unknown currency {}", currency),
+            ));
+        }
+    };
+    let qr_type = match unsafe { optional_str(request.amount) }? {
+        None => PointOfInitialMethod::Static(currency),
+        Some(amount) => {
+            let amount = TransactionAmount::from_string(&currency, amount).map_err(fail_with)?;
+            let additional_data_field = match request.expiration_timestamp_ms {
+                0 => None,
+                millis => {
+                    let expiration =
+                        Utc.timestamp_millis_opt(millis).single().ok_or_else(|| {
+                            fail(KhqrStatus::InvalidInput, "Invalid expiration timestamp")
+                        })?;
+                    Some(AdditionalDataField::new(expiration).map_err(fail_with)?)
+                }
+            };
+            PointOfInitialMethod::Dynamic {
+                additional_data_field,
+                amount,
+            }
+        }
+    };
+
+    let merchant_city = match unsafe { optional_str(request.merchant_city) }? {
+        Some(city) => Some(MerchantCity::from_string(city).ok_or_else(|| {
+            fail(
+                KhqrStatus::InvalidInput,
+                format!("Unknown merchant city '{}'", city),
+            )
+        })?),
+        None => None,
+    };
+
+    let additional_data_template = AdditionalDataTemplate {
+        bill_number: owned(request.bill_number)?,
+        store_label: owned(request.store_label)?,
+        terminal_label: owned(request.terminal_label)?,
+        mobile_number: owned(request.mobile_number)?,
+        purpose_of_transaction: owned(request.purpose_of_transaction)?,
+    };
+    let has_additional_data = additional_data_template.bill_number.is_some()
+        || additional_data_template.store_label.is_some()
+        || additional_data_template.terminal_label.is_some()
+        || additional_data_template.mobile_number.is_some()
+        || additional_data_template.purpose_of_transaction.is_some();
+
+    Ok(Bakong {
+        qr_type,
+        merchant_type,
+        merchant_category_code: owned(request.merchant_category_code)?,
+        tip_or_convenience_fee: None,
+        merchant_name: unsafe { required_str(request.merchant_name, "merchant_name") }?.to_string(),
+        merchant_city,
+        postal_code: None,
+        country_code: None,
+        additional_data_template: has_additional_data.then_some(additional_data_template),
+        unionpay_merchant: None,
+        network_merchant_accounts: Vec::new(),
+        merchant_account_templates: Vec::new(),
+        merchant_information_language_template: None,
+        unreserved_templates: Vec::new(),
+        extensions: Extensions::default(),
+    })
+}
+
+/// Generates a payload from `request` into `*out`, which must be released with `khqr_string_free`.
+///
+/// # Safety
+/// `request` must point to a valid `KhqrRequest` and `out` to writable storage for a pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn khqr_generate(
+    request: *const KhqrRequest,
+    out: *mut *mut c_char,
+) -> KhqrStatus {
+    catch_status(|| {
+        if request.is_null() || out.is_null() {
+            return fail(KhqrStatus::NullPointer, "request and out must not be NULL");
+        }
+
+        let bakong = match unsafe { build_request(&*request) } {
+            Ok(bakong) => bakong,
+            Err(status) => return status,
+        };
+        match bakong.generate_qr() {
+            Ok(qr) => {
+                let Ok(qr) = CString::new(qr) else {
+                    return fail(KhqrStatus::InvalidData, "Payload contains a NUL byte");
+                };
+                unsafe { *out = qr.into_raw() };
+                KhqrStatus::Ok
+            }
+            Err(e) => fail_with(e),
+        }
+    })
+}
+
+/// Decodes `qr` into `*out`, which must be released with `khqr_free`. Payloads that are not
+/// ASCII are rejected with `KHQR_STATUS_INVALID_INPUT`.
+///
+/// # Safety
+/// `qr` must be a NUL-terminated string and `out` must be writable storage for a pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn khqr_decode(qr: *const c_char, out: *mut *mut KhqrPayload) -> KhqrStatus {
+    catch_status(|| {
+        if out.is_null() {
+            return fail(KhqrStatus::NullPointer, "out must not be NULL");
+        }
+        let qr = match unsafe { required_payload(qr) } {
+            Ok(qr) => qr,
+            Err(status) => return status,
+        };
+
+        match Bakong::decode_qr(qr) {
+            Ok(bakong) => {
+                unsafe { *out = Box::into_raw(Box::new(KhqrPayload::new(bakong))) };
+                KhqrStatus::Ok
+            }
+            Err(e) => fail_with(e),
+        }
+    })
+}
+
+/// Returns `KHQR_STATUS_OK` when `qr` decodes and its CRC matches, otherwise the first error.
+/// Warnings, such as tags out of canonical order, do not fail validation.
+///
+/// # Safety
+/// `qr` must be a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn khqr_validate(qr: *const c_char) -> KhqrStatus {
+    catch_status(|| {
+        let qr = match unsafe { required_payload(qr) } {
+            Ok(qr) => qr,
+            Err(status) => return status,
+        };
+
+        let mut findings = validation::validate(qr).into_iter();
+        match findings.find(|finding| finding.severity == Severity::Error) {
+            Some(finding) => fail(KhqrStatus::InvalidData, finding.message),
+            None => KhqrStatus::Ok,
+        }
+    })
+}
+
+/// Computes the CRC-16/CCITT-FALSE checksum used by tag 63.
+///
+/// # Safety
+/// `data` must be a NUL-terminated string and `out` must be writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn khqr_crc(data: *const c_char, out: *mut u16) -> KhqrStatus {
+    catch_status(|| {
+        if out.is_null() {
+            return fail(KhqrStatus::NullPointer, "out must not be NULL");
+        }
+        match unsafe { required_str(data, "data") } {
+            Ok(data) => {
+                unsafe { *out = crc16_ccitt(data) };
+                KhqrStatus::Ok
+            }
+            Err(status) => status,
+        }
+    })
+}
+
+/// Stores a field of a decoded payload in `*out`, or NULL when absent. The string is valid until
+/// `khqr_free`. `field` is a `KhqrField`; any other value returns `KHQR_STATUS_INVALID_INPUT`.
+///
+/// # Safety
+/// `payload` must be NULL or a pointer returned by `khqr_decode`, and `out` must be writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn khqr_payload_field(
+    payload: *const KhqrPayload,
+    field: c_int,
+    out: *mut *const c_char,
+) -> KhqrStatus {
+    catch_status(|| {
+        let Some(payload) = (unsafe { payload.as_ref() }) else {
+            return fail(KhqrStatus::NullPointer, "payload must not be NULL");
+        };
+        if out.is_null() {
+            return fail(KhqrStatus::NullPointer, "out must not be NULL");
+        }
+        let Some(value) = usize::try_from(field)
+            .ok()
+            .and_then(|index| payload.fields.get(index))
+        else {
+            return fail(KhqrStatus::InvalidInput, format!("Unknown field {}", field));
+        };
+        unsafe { *out = value.as_ref().map_or(ptr::null(), |value| value.as_ptr()) };
+        KhqrStatus::Ok
+    })
+}
+
+/// # Safety
+/// `payload` must be NULL or a pointer returned by `khqr_decode`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn khqr_payload_is_static(payload: *const KhqrPayload) -> bool {
+    catch(
+        || false,
+        || unsafe { payload.as_ref() }.is_some_and(|payload| payload.bakong.qr_type.is_static()),
+    )
+}
+
+/// # Safety
+/// `payload` must be a pointer returned by `khqr_decode`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn khqr_payload_currency(payload: *const KhqrPayload) -> KhqrCurrency {
+    catch(
+        || KhqrCurrency::Khr,
+        || {
+            let code = unsafe { payload.as_ref() }.map(|payload| match &payload.bakong.qr_type {
+                PointOfInitialMethod::Static(currency) => currency.currency_code(),
+                PointOfInitialMethod::Dynamic { amount, .. } => amount.currency_code(),
+            });
+            match code {
+                Some("840") => KhqrCurrency::Usd,
+                _ => KhqrCurrency::Khr,
+            }
+        },
+    )
+}
+
+/// Returns the tag 99 expiry in Unix milliseconds, or 0 when absent.
+///
+/// # Safety
+/// `payload` must be NULL or a pointer returned by `khqr_decode`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn khqr_payload_expiration_ms(payload: *const KhqrPayload) -> i64 {
+    catch(
+        || 0,
+        || match unsafe { payload.as_ref() }.map(|payload| &payload.bakong.qr_type) {
+            Some(PointOfInitialMethod::Dynamic {
+                additional_data_field: Some(field),
+                ..
+            }) => field.expiration_timestamp.timestamp_millis(),
+            _ => 0,
+        },
+    )
+}
+
+/// Returns the message for the last failed call on this thread, or NULL.
+#[unsafe(no_mangle)]
+pub extern "C" fn khqr_last_error() -> *const c_char {
+    catch(ptr::null, || {
+        LAST_ERROR.with(|last| {
+            last.borrow()
+                .as_ref()
+                .map_or(ptr::null(), |message| message.as_ptr())
+        })
+    })
+}
+
+/// # Safety
+/// `payload` must be NULL or a pointer returned by `khqr_decode` that was not freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn khqr_free(payload: *mut KhqrPayload) {
+    catch(
+        || (),
+        || {
+            if !payload.is_null() {
+                drop(unsafe { Box::from_raw(payload) });
+            }
+        },
+    )
+}
+
+/// # Safety
+/// `value` must be NULL or a string returned by `khqr_generate` that was not freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn khqr_string_free(value: *mut c_char) {
+    catch(
+        || (),
+        || {
+            if !value.is_null() {
+                drop(unsafe { CString::from_raw(value) });
+            }
+        },
+    )
+}
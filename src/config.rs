@@ -0,0 +1,529 @@
+use crate::error::{Error, ErrorKind};
+use crate::extension::Extensions;
+use crate::models::{
+    AdditionalDataTemplate, Bakong, CountryCode, IndivualInformation, MerchantCity,
+    MerchantInformation, MerchantInformationLanguageTemplate, MerchantLocation, MerchantType,
+    PointOfInitialMethod, SubTags, Tags, validate_postal_code,
+};
+use serde::Deserialize;
+use std::path::Path;
+
+const ENV_PREFIX: &str = "KHQR_";
+
+#[derive(Debug, Clone)]
+pub struct MerchantConfig {
+    pub stores: Vec<MerchantProfile>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MerchantProfile {
+    pub store_id: String,
+    pub merchant_type: MerchantType,
+    pub merchant_name: String,
+    pub merchant_city: Option<MerchantCity>,
+    pub postal_code: Option<String>,
+    /// District and commune for regulator reporting; never written to the QR.
+    pub district: Option<String>,
+    pub commune: Option<String>,
+    pub merchant_category_code: Option<String>,
+    pub store_label: Option<String>,
+    pub mobile_number: Option<String>,
+    pub terminals: Vec<String>,
+    pub merchant_information_language_template: Option<MerchantInformationLanguageTemplate>,
+}
+
+impl MerchantConfig {
+    /// Reads `path` and applies `KHQR_<STORE>_<FIELD>` overrides from the process environment.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let mut config = Self::from_path(path)?;
+        config.apply_env_overrides(std::env::vars())?;
+        Ok(config)
+    }
+
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+        let origin = path.display().to_string();
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Self::parse_toml(&origin, &source),
+            Some("json") => Self::parse_json(&origin, &source),
+            _ => Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("{}: expected a .toml or .json file", origin),
+            )),
+        }
+    }
+
+    pub fn from_toml_str(source: &str) -> Result<Self, Error> {
+        Self::parse_toml("<toml>", source)
+    }
+
+    pub fn from_json_str(source: &str) -> Result<Self, Error> {
+        Self::parse_json("<json>", source)
+    }
+
+    pub fn store(&self, store_id: &str) -> Option<&MerchantProfile> {
+        self.stores.iter().find(|store| store.store_id == store_id)
+    }
+
+    pub fn apply_env_overrides<I>(&mut self, vars: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        for (name, value) in vars {
+            let Some(rest) = name.strip_prefix(ENV_PREFIX) else {
+                continue;
+            };
+
+            // Store ids may prefix one another (`a` and `a-b`), so the longest match wins.
+            let Some((store, field)) = self
+                .stores
+                .iter_mut()
+                .filter_map(|store| {
+                    let key = env_store_key(&store.store_id);
+                    let field = rest.strip_prefix(&key)?.strip_prefix('_')?;
+                    Some((key.len(), store, field))
+                })
+                .max_by_key(|(len, _, _)| *len)
+                .map(|(_, store, field)| (store, field))
+            else {
+                continue;
+            };
+
+            store
+                .apply_override(field, &value)
+                .map_err(|msg| Error::new(ErrorKind::InvalidData, format!("{}: {}", name, msg)))?;
+        }
+
+        for store in &self.stores {
+            if let Some(language) = &store.merchant_information_language_template
+                && (language.language_preference.is_empty()
+                    || language.merchant_name_alternate_language.is_empty()
+                    || language.merchant_city_alternate_language.is_empty())
+            {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!(
+                        "store '{}': LANGUAGE_PREFERENCE, LANGUAGE_MERCHANT_NAME and \
+                         LANGUAGE_MERCHANT_CITY must be overridden together",
+                        store.store_id
+                    ),
+                ));
+            }
+            store.location().map_err(|e| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("store '{}': {}", store.store_id, e),
+                )
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn parse_toml(origin: &str, source: &str) -> Result<Self, Error> {
+        let raw: RawConfig = toml::from_str(source).map_err(|e| {
+            let (line, column) = e
+                .span()
+                .map(|span| line_column(source, span.start))
+                .unwrap_or((1, 1));
+            located_error(origin, line, column, e.message())
+        })?;
+
+        Self::from_raw(origin, raw)
+    }
+
+    fn parse_json(origin: &str, source: &str) -> Result<Self, Error> {
+        let raw: RawConfig = serde_json::from_str(source).map_err(|e| {
+            let message = e.to_string();
+            let suffix = format!(" at line {} column {}", e.line(), e.column());
+            let message = message.strip_suffix(&suffix).unwrap_or(&message);
+            located_error(origin, e.line(), e.column(), message)
+        })?;
+
+        Self::from_raw(origin, raw)
+    }
+
+    fn from_raw(origin: &str, raw: RawConfig) -> Result<Self, Error> {
+        let mut stores: Vec<MerchantProfile> = Vec::with_capacity(raw.stores.len());
+
+        for store in raw.stores {
+            if store.id.is_empty() {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("{}: store id must not be empty", origin),
+                ));
+            }
+            if stores.iter().any(|s| s.store_id == store.id) {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("{}: duplicate store id '{}'", origin, store.id),
+                ));
+            }
+            let profile = store.into_profile();
+            profile.location().map_err(|e| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("{}: store '{}': {}", origin, profile.store_id, e),
+                )
+            })?;
+            stores.push(profile);
+        }
+
+        Ok(Self { stores })
+    }
+}
+
+impl MerchantProfile {
+    /// The store's province, district and commune, or `None` without a merchant city.
+    pub fn location(&self) -> Result<Option<MerchantLocation>, Error> {
+        let Some(province) = &self.merchant_city else {
+            if self.district.is_some() || self.commune.is_some() {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "district and commune require a merchant city",
+                ));
+            }
+            return Ok(None);
+        };
+        MerchantLocation::new(
+            province.clone(),
+            self.district.clone(),
+            self.commune.clone(),
+        )
+        .map(Some)
+    }
+
+    pub fn to_bakong(
+        &self,
+        terminal: Option<&str>,
+        qr_type: PointOfInitialMethod,
+    ) -> Result<Bakong, Error> {
+        if let Some(terminal) = terminal
+            && !self.terminals.iter().any(|t| t == terminal)
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Terminal '{}' is not configured for store '{}'",
+                    terminal, self.store_id
+                ),
+            ));
+        }
+
+        let additional_data_template =
+            if self.store_label.is_some() || self.mobile_number.is_some() || terminal.is_some() {
+                Some(AdditionalDataTemplate {
+                    bill_number: None,
+                    store_label: self.store_label.clone(),
+                    terminal_label: terminal.map(str::to_string),
+                    mobile_number: self.mobile_number.clone(),
+                    purpose_of_transaction: None,
+                })
+            } else {
+                None
+            };
+
+        Ok(Bakong {
+            qr_type,
+            merchant_type: self.merchant_type.clone(),
+            merchant_category_code: self.merchant_category_code.clone(),
+            tip_or_convenience_fee: None,
+            merchant_name: self.merchant_name.clone(),
+            merchant_city: self.merchant_city.clone(),
+            postal_code: self.postal_code.clone(),
+            country_code: None,
+            additional_data_template,
+            unionpay_merchant: None,
+            network_merchant_accounts: Vec::new(),
+            merchant_account_templates: Vec::new(),
+            merchant_information_language_template: self
+                .merchant_information_language_template
+                .clone(),
+            unreserved_templates: Vec::new(),
+            extensions: Extensions::default(),
+        })
+    }
+
+    fn apply_override(&mut self, field: &str, value: &str) -> Result<(), String> {
+        let value = value.to_string();
+        match field {
+            "BAKONG_ACCOUNT_IDENTIFIER" => {
+                let BakongAccount(account) = value.try_into()?;
+                match &mut self.merchant_type {
+                    MerchantType::Indivual(info) => info.bakong_account_identifier = account,
+                    MerchantType::Merchant(info) => info.bakong_account_identifier = account,
+                }
+            }
+            "MERCHANT_IDENTIFIER" => {
+                let MerchantId(id) = value.try_into()?;
+                match &mut self.merchant_type {
+                    MerchantType::Merchant(info) => info.merchant_identifier = id,
+                    MerchantType::Indivual(_) => {
+                        return Err("merchant identifier is not used by individual accounts".into());
+                    }
+                }
+            }
+            "ACQUIRING_BANK" => {
+                let AcquiringBank(bank) = value.try_into()?;
+                match &mut self.merchant_type {
+                    MerchantType::Indivual(info) => info.acquiring_bank = Some(bank),
+                    MerchantType::Merchant(info) => info.acquiring_bank = bank,
+                }
+            }
+            "MERCHANT_NAME" => {
+                let MerchantName(name) = value.try_into()?;
+                self.merchant_name = name;
+            }
+            "MERCHANT_CITY" => {
+                let City(city) = value.try_into()?;
+                self.merchant_city = Some(city);
+            }
+            "POSTAL_CODE" => {
+                let PostalCode(code) = value.try_into()?;
+                self.postal_code = Some(code);
+            }
+            "MERCHANT_CATEGORY_CODE" => {
+                let CategoryCode(code) = value.try_into()?;
+                self.merchant_category_code = Some(code);
+            }
+            "STORE_LABEL" => {
+                let StoreLabel(label) = value.try_into()?;
+                self.store_label = Some(label);
+            }
+            "MOBILE_NUMBER" => {
+                let MobileNumber(number) = value.try_into()?;
+                self.mobile_number = Some(number);
+            }
+            "TERMINALS" => {
+                self.terminals = value
+                    .split(',')
+                    .map(|label| TerminalLabel::try_from(label.trim().to_string()).map(|t| t.0))
+                    .collect::<Result<_, _>>()?;
+            }
+            "LANGUAGE_PREFERENCE" => {
+                let LanguagePreference(preference) = value.try_into()?;
+                self.language_template().language_preference = preference;
+            }
+            "LANGUAGE_MERCHANT_NAME" => {
+                let AlternateName(name) = value.try_into()?;
+                self.language_template().merchant_name_alternate_language = name;
+            }
+            "LANGUAGE_MERCHANT_CITY" => {
+                let AlternateCity(city) = value.try_into()?;
+                self.language_template().merchant_city_alternate_language = city;
+            }
+            _ => return Err(format!("unknown field '{}'", field)),
+        }
+        Ok(())
+    }
+
+    /// The store's language template, started empty when the file has none so that overrides
+    /// can add one.
+    fn language_template(&mut self) -> &mut MerchantInformationLanguageTemplate {
+        self.merchant_information_language_template
+            .get_or_insert_with(|| MerchantInformationLanguageTemplate {
+                language_preference: String::new(),
+                merchant_name_alternate_language: String::new(),
+                merchant_city_alternate_language: String::new(),
+            })
+    }
+}
+
+macro_rules! checked_string {
+    ($name:ident, $rule:expr) => {
+        #[derive(Deserialize)]
+        #[serde(try_from = "String")]
+        struct $name(String);
+
+        impl TryFrom<String> for $name {
+            type Error = String;
+
+            fn try_from(value: String) -> Result<Self, Self::Error> {
+                $rule.validate_length(&value)?;
+                Ok(Self(value))
+            }
+        }
+    };
+}
+
+checked_string!(MerchantId, SubTags::MerchantIdentifier);
+checked_string!(AcquiringBank, SubTags::AcquiringBank);
+checked_string!(AccountInformation, SubTags::IndividualAccountInformation);
+checked_string!(MerchantName, Tags::MerchantName);
+checked_string!(CategoryCode, Tags::MerchantCategoryCode);
+checked_string!(StoreLabel, SubTags::StoreLabel);
+checked_string!(TerminalLabel, SubTags::TerminalLabel);
+checked_string!(MobileNumber, SubTags::MobileNumber);
+checked_string!(LanguagePreference, SubTags::LanguagePreference);
+checked_string!(AlternateName, SubTags::MerchantNameAlternateLanguage);
+checked_string!(AlternateCity, SubTags::MerchantCityAlternateLanguage);
+
+#[derive(Deserialize)]
+#[serde(try_from = "String")]
+struct BakongAccount(String);
+
+impl TryFrom<String> for BakongAccount {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        SubTags::BakongAccountIdentifier.validate_length(&value)?;
+        if value.matches('@').count() != 1 {
+            return Err("Bakong account identifier must contain exactly one '@'".to_string());
+        }
+        Ok(Self(value))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(try_from = "String")]
+struct PostalCode(String);
+
+impl TryFrom<String> for PostalCode {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        validate_postal_code(&value, &CountryCode::KH).map_err(|e| e.to_string())?;
+        Ok(Self(value))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(try_from = "String")]
+struct City(MerchantCity);
+
+impl TryFrom<String> for City {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        MerchantCity::from_string(&value)
+            .map(City)
+            .ok_or_else(|| format!("Unknown merchant city '{}'", value))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawConfig {
+    #[serde(default)]
+    stores: Vec<RawStore>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawStore {
+    id: String,
+    merchant_name: MerchantName,
+    merchant_city: Option<City>,
+    postal_code: Option<PostalCode>,
+    district: Option<String>,
+    commune: Option<String>,
+    merchant_category_code: Option<CategoryCode>,
+    store_label: Option<StoreLabel>,
+    mobile_number: Option<MobileNumber>,
+    #[serde(default)]
+    terminals: Vec<TerminalLabel>,
+    account: RawAccount,
+    language: Option<RawLanguage>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase", deny_unknown_fields)]
+enum RawAccount {
+    Individual {
+        bakong_account_identifier: BakongAccount,
+        individual_account_information: Option<AccountInformation>,
+        acquiring_bank: Option<AcquiringBank>,
+    },
+    Merchant {
+        bakong_account_identifier: BakongAccount,
+        merchant_identifier: MerchantId,
+        acquiring_bank: AcquiringBank,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawLanguage {
+    preference: LanguagePreference,
+    merchant_name: AlternateName,
+    merchant_city: AlternateCity,
+}
+
+impl RawStore {
+    fn into_profile(self) -> MerchantProfile {
+        let merchant_type = match self.account {
+            RawAccount::Individual {
+                bakong_account_identifier,
+                individual_account_information,
+                acquiring_bank,
+            } => MerchantType::Indivual(IndivualInformation {
+                bakong_account_identifier: bakong_account_identifier.0,
+                individual_account_information: individual_account_information.map(|v| v.0),
+                acquiring_bank: acquiring_bank.map(|v| v.0),
+            }),
+            RawAccount::Merchant {
+                bakong_account_identifier,
+                merchant_identifier,
+                acquiring_bank,
+            } => MerchantType::Merchant(MerchantInformation {
+                bakong_account_identifier: bakong_account_identifier.0,
+                merchant_identifier: merchant_identifier.0,
+                acquiring_bank: acquiring_bank.0,
+            }),
+        };
+
+        MerchantProfile {
+            store_id: self.id,
+            merchant_type,
+            merchant_name: self.merchant_name.0,
+            merchant_city: self.merchant_city.map(|v| v.0),
+            postal_code: self.postal_code.map(|v| v.0),
+            district: self.district,
+            commune: self.commune,
+            merchant_category_code: self.merchant_category_code.map(|v| v.0),
+            store_label: self.store_label.map(|v| v.0),
+            mobile_number: self.mobile_number.map(|v| v.0),
+            terminals: self.terminals.into_iter().map(|v| v.0).collect(),
+            merchant_information_language_template: self.language.map(|language| {
+                MerchantInformationLanguageTemplate {
+                    language_preference: language.preference.0,
+                    merchant_name_alternate_language: language.merchant_name.0,
+                    merchant_city_alternate_language: language.merchant_city.0,
+                }
+            }),
+        }
+    }
+}
+
+fn env_store_key(store_id: &str) -> String {
+    store_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset.min(source.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rfind('\n')
+        .map_or(before.len(), |i| before.len() - i - 1)
+        + 1;
+    (line, column)
+}
+
+fn located_error(origin: &str, line: usize, column: usize, message: &str) -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        format!("{}:{}:{}: {}", origin, line, column, message),
+    )
+}
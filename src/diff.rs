@@ -0,0 +1,286 @@
+use crate::error::Error;
+use crate::models::{SubTags, Tags, TransactionCurrency};
+use crate::scheme::decode_any_at;
+use crate::tlv::{is_template, split_tlv};
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use chrono::{DateTime, TimeZone, Utc};
+use core::fmt::{self, Display};
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Change {
+    Added {
+        path: String,
+        value: String,
+    },
+    Removed {
+        path: String,
+        value: String,
+    },
+    Changed {
+        path: String,
+        old: String,
+        new: String,
+    },
+}
+
+impl Change {
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Added { path, .. } | Self::Removed { path, .. } | Self::Changed { path, .. } => {
+                path
+            }
+        }
+    }
+}
+
+/// Differences between two payloads, tag by tag, with human-readable notes on what they mean.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PayloadDiff {
+    pub changes: Vec<Change>,
+    pub notes: Vec<String>,
+}
+
+impl PayloadDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.notes.is_empty()
+    }
+}
+
+impl Display for PayloadDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            match change {
+                Change::Added { path, value } => writeln!(f, "+ {}: {:?}", path, value)?,
+                Change::Removed { path, value } => writeln!(f, "- {}: {:?}", path, value)?,
+                Change::Changed { path, old, new } => {
+                    writeln!(f, "~ {}: {:?} -> {:?}", path, old, new)?
+                }
+            }
+        }
+        for note in &self.notes {
+            writeln!(f, "note: {}", note)?;
+        }
+        Ok(())
+    }
+}
+
+type Fields<'a> = BTreeMap<(u8, Option<u8>), &'a str>;
+
+#[cfg(feature = "std")]
+pub fn diff(old: &str, new: &str, include_crc: bool) -> Result<PayloadDiff, Error> {
+    diff_at(old, new, include_crc, Utc::now())
+}
+
+/// Compares `old` and `new` by tag and sub-tag path; tag 63 is only compared when `include_crc`
+/// is set. Both payloads are also decoded, treating `now` as the current time, and a payload
+/// that fails to decode (a CRC mismatch included) or repeats a tag is reported in the notes.
+pub fn diff_at(
+    old: &str,
+    new: &str,
+    include_crc: bool,
+    now: DateTime<Utc>,
+) -> Result<PayloadDiff, Error> {
+    let mut result = PayloadDiff::default();
+    for (label, qr) in [("old", old), ("new", new)] {
+        if let Err(e) = decode_any_at(qr, now) {
+            result
+                .notes
+                .push(format!("{} payload does not decode: {}", label, e));
+        }
+    }
+    let old_fields = flatten(old, "old", &mut result.notes)?;
+    let new_fields = flatten(new, "new", &mut result.notes)?;
+
+    let mut paths: Vec<&(u8, Option<u8>)> = old_fields.keys().chain(new_fields.keys()).collect();
+    paths.sort();
+    paths.dedup();
+
+    for key in paths {
+        if key.0 == Tags::Crc.code() && !include_crc {
+            continue;
+        }
+        let path = path(*key);
+        match (old_fields.get(key), new_fields.get(key)) {
+            (Some(old), Some(new)) if old != new => result.changes.push(Change::Changed {
+                path,
+                old: old.to_string(),
+                new: new.to_string(),
+            }),
+            (Some(old), None) => result.changes.push(Change::Removed {
+                path,
+                value: old.to_string(),
+            }),
+            (None, Some(new)) => result.changes.push(Change::Added {
+                path,
+                value: new.to_string(),
+            }),
+            _ => {}
+        }
+    }
+
+    explain(&old_fields, &new_fields, &mut result.notes);
+    Ok(result)
+}
+
+fn path((tag, sub_tag): (u8, Option<u8>)) -> String {
+    match sub_tag {
+        Some(sub_tag) => format!("{:02}.{:02}", tag, sub_tag),
+        None => format!("{:02}", tag),
+    }
+}
+
+/// Keys `qr` by path. A repeated tag or sub-tag keeps its first value for the comparison and is
+/// reported in `notes` with both values, so that the diff never hides the second one.
+fn flatten<'a>(qr: &'a str, label: &str, notes: &mut Vec<String>) -> Result<Fields<'a>, Error> {
+    let mut fields = Fields::new();
+    let mut tags: BTreeMap<u8, &str> = BTreeMap::new();
+    let mut repeated = |key: (u8, Option<u8>), first: &str, value: &str| {
+        notes.push(format!(
+            "{} payload repeats {}: {:?}, then {:?}",
+            label,
+            path(key),
+            first,
+            value
+        ))
+    };
+
+    for (tag, value) in split_tlv(qr)? {
+        if let Some(first) = tags.get(&tag) {
+            repeated((tag, None), first, value);
+            continue;
+        }
+        tags.insert(tag, value);
+
+        match split_tlv(value) {
+            Ok(sub_fields) if is_template(tag) => {
+                for (sub_tag, sub_value) in sub_fields {
+                    let key = (tag, Some(sub_tag));
+                    match fields.get(&key) {
+                        Some(first) => repeated(key, first, sub_value),
+                        None => {
+                            fields.insert(key, sub_value);
+                        }
+                    }
+                }
+            }
+            _ => {
+                fields.insert((tag, None), value);
+            }
+        }
+    }
+    Ok(fields)
+}
+
+fn explain(old: &Fields<'_>, new: &Fields<'_>, notes: &mut Vec<String>) {
+    fn top<'a>(fields: &Fields<'a>, tag: Tags) -> Option<&'a str> {
+        fields.get(&(tag.code(), None)).copied()
+    }
+
+    fn sub<'a>(fields: &Fields<'a>, tag: Tags, sub_tag: SubTags) -> Option<&'a str> {
+        fields.get(&(tag.code(), Some(sub_tag.code()))).copied()
+    }
+
+    let old_method = top(old, Tags::PointOfInitialMethod);
+    let new_method = top(new, Tags::PointOfInitialMethod);
+    if old_method != new_method {
+        notes.push(format!(
+            "QR changed from {} to {}",
+            describe_method(old_method),
+            describe_method(new_method)
+        ));
+    }
+
+    let old_amount = describe_amount(
+        top(old, Tags::TransactionAmount),
+        top(old, Tags::TransactionCurrency),
+    );
+    let new_amount = describe_amount(
+        top(new, Tags::TransactionAmount),
+        top(new, Tags::TransactionCurrency),
+    );
+    if old_amount != new_amount {
+        notes.push(format!(
+            "amount changed from {} to {}",
+            old_amount, new_amount
+        ));
+    }
+
+    let old_account = account(old);
+    let new_account = account(new);
+    if old_account != new_account {
+        notes.push(format!(
+            "Bakong account changed from {} to {}",
+            describe_account(old_account),
+            describe_account(new_account)
+        ));
+    }
+
+    let old_expiry = sub(old, Tags::AdditionalDataField, SubTags::ExpirationTimestamp);
+    let new_expiry = sub(new, Tags::AdditionalDataField, SubTags::ExpirationTimestamp);
+    match (old_expiry, new_expiry) {
+        (Some(old), Some(new)) if old != new => notes.push(format!(
+            "expiry moved from {} to {}",
+            describe_timestamp(old),
+            describe_timestamp(new)
+        )),
+        (Some(old), None) => notes.push(format!("expiry {} removed", describe_timestamp(old))),
+        (None, Some(new)) => notes.push(format!("expiry set to {}", describe_timestamp(new))),
+        _ => {}
+    }
+}
+
+fn describe_method(value: Option<&str>) -> &str {
+    match value {
+        Some("11") => "static",
+        Some("12") => "dynamic",
+        Some(other) => other,
+        None => "unspecified",
+    }
+}
+
+fn describe_amount(amount: Option<&str>, currency: Option<&str>) -> String {
+    let currency = currency.map(|code| match TransactionCurrency::from_string(code) {
+        Ok(currency) => format!("{:?}", currency),
+        Err(_) => format!("currency {}", code),
+    });
+    match (amount, currency) {
+        (Some(amount), Some(currency)) => format!("{} {}", amount, currency),
+        (Some(amount), None) => format!("{} (no currency)", amount),
+        (None, Some(currency)) => format!("no amount ({})", currency),
+        (None, None) => "no amount".to_string(),
+    }
+}
+
+fn account<'a>(fields: &Fields<'a>) -> Option<(&'static str, &'a str)> {
+    let account_code = SubTags::BakongAccountIdentifier.code();
+    if let Some(id) = fields.get(&(
+        Tags::MerchantAccountInfoIndividual.code(),
+        Some(account_code),
+    )) {
+        return Some(("individual", id));
+    }
+    fields
+        .get(&(Tags::MerchantAccountInfoMerchant.code(), Some(account_code)))
+        .map(|id| ("merchant", *id))
+}
+
+fn describe_account(account: Option<(&str, &str)>) -> String {
+    match account {
+        Some((kind, id)) => format!("{} ({})", id, kind),
+        None => "none".to_string(),
+    }
+}
+
+fn describe_timestamp(millis: &str) -> String {
+    millis
+        .parse::<i64>()
+        .ok()
+        .and_then(|millis| Utc.timestamp_millis_opt(millis).single())
+        .map(|timestamp| timestamp.to_string())
+        .unwrap_or_else(|| millis.to_string())
+}
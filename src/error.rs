@@ -0,0 +1,51 @@
+#[cfg(feature = "std")]
+pub use std::io::{Error, ErrorKind};
+
+#[cfg(not(feature = "std"))]
+pub use no_std::{Error, ErrorKind};
+
+#[cfg(not(feature = "std"))]
+mod no_std {
+    use alloc::string::{String, ToString};
+    use core::fmt;
+
+    /// Mirrors the subset of `std::io::ErrorKind` the SDK reports.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ErrorKind {
+        InvalidInput,
+        InvalidData,
+        Other,
+    }
+
+    /// Stand-in for `std::io::Error` when the `std` feature is disabled.
+    #[derive(Debug)]
+    pub struct Error {
+        kind: ErrorKind,
+        message: String,
+    }
+
+    impl Error {
+        pub fn new<M: fmt::Display>(kind: ErrorKind, message: M) -> Self {
+            Self {
+                kind,
+                message: message.to_string(),
+            }
+        }
+
+        pub fn other<M: fmt::Display>(message: M) -> Self {
+            Self::new(ErrorKind::Other, message)
+        }
+
+        pub fn kind(&self) -> ErrorKind {
+            self.kind
+        }
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(&self.message)
+        }
+    }
+
+    impl core::error::Error for Error {}
+}
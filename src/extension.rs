@@ -0,0 +1,442 @@
+use crate::error::{Error, ErrorKind};
+use crate::models::{Bakong, Tags};
+use crate::tlv::{LengthCounter, TLV, split_tlv};
+use crate::write_tlv;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use chrono::{DateTime, Utc};
+use core::any::{Any, TypeId};
+use core::fmt::{self, Debug, Write};
+
+/// Where an extension value is carried in the payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtensionTag {
+    /// A whole top-level tag: a merchant account template other than Bakong's (26–28, 31–51) or
+    /// an unreserved template (80–98).
+    Tag(u8),
+    /// A sub-tag of tag 62 (10–99), of an unreserved template (01–99) or of tag 99 (02–99).
+    SubTag(u8, u8),
+}
+
+impl ExtensionTag {
+    pub fn path(&self) -> String {
+        match self {
+            Self::Tag(tag) => format!("{:02}", tag),
+            Self::SubTag(tag, sub_tag) => format!("{:02}.{:02}", tag, sub_tag),
+        }
+    }
+
+    /// Whether the SDK leaves this tag to extensions. Tags and sub-tags it models itself are not.
+    pub fn is_available(&self) -> bool {
+        match *self {
+            Self::Tag(tag) => matches!(tag, 26..=28 | 31..=51 | 80..=98),
+            Self::SubTag(62, sub_tag) => (10..=99).contains(&sub_tag),
+            Self::SubTag(80..=98, sub_tag) => (1..=99).contains(&sub_tag),
+            Self::SubTag(99, sub_tag) => (2..=99).contains(&sub_tag),
+            Self::SubTag(..) => false,
+        }
+    }
+
+    fn parent(&self) -> u8 {
+        match *self {
+            Self::Tag(tag) | Self::SubTag(tag, _) => tag,
+        }
+    }
+}
+
+/// A partner-specific value carried in a tag the SDK does not model. Register the type with an
+/// `ExtensionRegistry` to have `decode_qr` parse it; `generate_qr` writes it back wherever it is
+/// stored in `Bakong::extensions`.
+pub trait TagExtension: Any + Clone + Debug + PartialEq + Send + Sync {
+    const TAG: ExtensionTag;
+
+    fn parse(value: &str) -> Result<Self, Error>;
+
+    fn write_value<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error>;
+}
+
+/// Object-safe view of a `TagExtension` so values of different types can share one list.
+trait StoredExtension: Any + Debug + Send + Sync {
+    fn tag(&self) -> ExtensionTag;
+    fn write_dyn(&self, writer: &mut dyn Write) -> Result<(), Error>;
+    fn clone_box(&self) -> Box<dyn StoredExtension>;
+    fn eq_dyn(&self, other: &dyn StoredExtension) -> bool;
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: TagExtension> StoredExtension for T {
+    fn tag(&self) -> ExtensionTag {
+        T::TAG
+    }
+
+    fn write_dyn(&self, writer: &mut dyn Write) -> Result<(), Error> {
+        self.write_value(writer)
+    }
+
+    fn clone_box(&self) -> Box<dyn StoredExtension> {
+        Box::new(self.clone())
+    }
+
+    fn eq_dyn(&self, other: &dyn StoredExtension) -> bool {
+        other.as_any().downcast_ref::<T>() == Some(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Extension values of a `Bakong`, at most one per tag, looked up by type.
+#[derive(Default)]
+#[cfg_attr(feature = "python", pyo3::pyclass(from_py_object))]
+pub struct Extensions {
+    values: Vec<Box<dyn StoredExtension>>,
+}
+
+impl Extensions {
+    pub fn get<T: TagExtension>(&self) -> Option<&T> {
+        self.values
+            .iter()
+            .find_map(|value| value.as_any().downcast_ref::<T>())
+    }
+
+    /// Stores `value`, replacing whatever was stored for the same tag.
+    pub fn insert<T: TagExtension>(&mut self, value: T) -> Result<(), Error> {
+        check_available(T::TAG)?;
+        self.insert_boxed(Box::new(value));
+        Ok(())
+    }
+
+    pub fn remove<T: TagExtension>(&mut self) -> Option<T> {
+        let index = self
+            .values
+            .iter()
+            .position(|value| value.as_any().is::<T>())?;
+        let value = self.values.remove(index);
+        value.as_any().downcast_ref::<T>().cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    fn insert_boxed(&mut self, value: Box<dyn StoredExtension>) {
+        self.values.retain(|stored| stored.tag() != value.tag());
+        self.values.push(value);
+    }
+
+    /// Whether an extension owns the whole top-level `tag`.
+    pub(crate) fn owns_tag(&self, tag: u8) -> bool {
+        self.find(ExtensionTag::Tag(tag)).is_some()
+    }
+
+    /// Writes top-level `tag` if an extension owns it.
+    pub(crate) fn write_tag<W: Write + ?Sized>(
+        &self,
+        writer: &mut W,
+        tag: u8,
+    ) -> Result<(), Error> {
+        match self.find(ExtensionTag::Tag(tag)) {
+            Some(value) => write_field(writer, tag, value),
+            None => Ok(()),
+        }
+    }
+
+    /// The lowest sub-tag stored under template `tag`.
+    pub(crate) fn first_sub_tag(&self, tag: u8) -> Option<u8> {
+        self.values
+            .iter()
+            .filter_map(|value| match value.tag() {
+                ExtensionTag::SubTag(parent, sub_tag) if parent == tag => Some(sub_tag),
+                _ => None,
+            })
+            .min()
+    }
+
+    /// Writes sub-tag `sub_tag` of template `tag` if an extension is stored there, returning
+    /// whether it wrote one.
+    pub(crate) fn write_sub_field<W: Write + ?Sized>(
+        &self,
+        writer: &mut W,
+        tag: u8,
+        sub_tag: u8,
+    ) -> Result<bool, Error> {
+        match self.find(ExtensionTag::SubTag(tag, sub_tag)) {
+            Some(value) => write_field(writer, sub_tag, value).map(|()| true),
+            None => Ok(false),
+        }
+    }
+
+    /// Writes every sub-tag stored under template `tag`, in ascending order.
+    pub(crate) fn write_sub_fields<W: Write + ?Sized>(
+        &self,
+        writer: &mut W,
+        tag: u8,
+    ) -> Result<(), Error> {
+        for sub_tag in 0..=99 {
+            self.write_sub_field(writer, tag, sub_tag)?;
+        }
+        Ok(())
+    }
+
+    fn find(&self, tag: ExtensionTag) -> Option<&dyn StoredExtension> {
+        self.values
+            .iter()
+            .find(|value| value.tag() == tag)
+            .map(|value| value.as_ref())
+    }
+
+    /// Drops every value stored under template `tag`.
+    pub(crate) fn remove_sub_fields(&mut self, tag: u8) {
+        self.values.retain(
+            |value| !matches!(value.tag(), ExtensionTag::SubTag(parent, _) if parent == tag),
+        );
+    }
+
+    /// Rejects a template that is owned whole by one extension and has sub-tags claimed by
+    /// another.
+    pub(crate) fn check_conflicts(&self) -> Result<(), Error> {
+        for value in &self.values {
+            let tag = value.tag();
+            if let ExtensionTag::SubTag(parent, _) = tag
+                && self
+                    .values
+                    .iter()
+                    .any(|other| other.tag() == ExtensionTag::Tag(parent))
+            {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!(
+                        "Extension {} is inside tag {:02}, which another extension owns",
+                        tag.path(),
+                        parent
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Clone for Extensions {
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values.iter().map(|value| value.clone_box()).collect(),
+        }
+    }
+}
+
+impl Debug for Extensions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(&self.values).finish()
+    }
+}
+
+impl PartialEq for Extensions {
+    fn eq(&self, other: &Self) -> bool {
+        self.values.len() == other.values.len()
+            && self
+                .values
+                .iter()
+                .all(|value| other.values.iter().any(|o| value.eq_dyn(o.as_ref())))
+    }
+}
+
+type Parser = fn(&str) -> Result<Box<dyn StoredExtension>, Error>;
+
+/// The `TagExtension` types to parse while decoding, at most one per tag.
+#[derive(Debug, Clone, Default)]
+pub struct ExtensionRegistry {
+    parsers: Vec<(ExtensionTag, TypeId, Parser)>,
+}
+
+impl ExtensionRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `T`, failing if its tag is modelled by the SDK or taken by another extension.
+    pub fn register<T: TagExtension>(&mut self) -> Result<&mut Self, Error> {
+        let tag = T::TAG;
+        check_available(tag)?;
+        if let Some((taken, ..)) = self.parsers.iter().find(|(registered, ..)| {
+            *registered == tag
+                || (registered.parent() == tag.parent()
+                    && (matches!(registered, ExtensionTag::Tag(_))
+                        || matches!(tag, ExtensionTag::Tag(_))))
+        }) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Extension {} overlaps the registered extension {}",
+                    tag.path(),
+                    taken.path()
+                ),
+            ));
+        }
+
+        self.parsers.push((tag, TypeId::of::<T>(), |value| {
+            Ok(Box::new(T::parse(value)?))
+        }));
+        Ok(self)
+    }
+
+    pub fn is_registered<T: TagExtension>(&self) -> bool {
+        self.parsers
+            .iter()
+            .any(|(_, type_id, _)| *type_id == TypeId::of::<T>())
+    }
+
+    #[cfg(feature = "std")]
+    pub fn decode_qr(&self, qr: &str) -> Result<Bakong, Error> {
+        self.decode_qr_at(qr, Utc::now())
+    }
+
+    /// Decodes `qr` like `Bakong::decode_qr_at`, parsing registered tags into
+    /// `Bakong::extensions` instead of rejecting them.
+    pub fn decode_qr_at(&self, qr: &str, now: DateTime<Utc>) -> Result<Bakong, Error> {
+        Bakong::decode_qr_with(qr, now, self)
+    }
+
+    /// Parses top-level `tag` into `extensions` if an extension owns it.
+    pub(crate) fn decode_tag(
+        &self,
+        tag: u8,
+        value: &str,
+        extensions: &mut Extensions,
+    ) -> Result<bool, Error> {
+        match self.parser(ExtensionTag::Tag(tag)) {
+            Some(parse) => {
+                extensions.insert_boxed(parse_at(parse, ExtensionTag::Tag(tag), value)?);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Parses the sub-tags of template `tag` that extensions own into `extensions` and returns
+    /// the rest of the template, or `None` when extensions claimed every sub-tag.
+    pub(crate) fn decode_sub_fields<'a>(
+        &self,
+        tag: u8,
+        value: &'a str,
+        extensions: &mut Extensions,
+    ) -> Result<Option<Cow<'a, str>>, Error> {
+        if !self
+            .parsers
+            .iter()
+            .any(|(registered, ..)| matches!(registered, ExtensionTag::SubTag(parent, _) if *parent == tag))
+        {
+            return Ok(Some(Cow::Borrowed(value)));
+        }
+
+        let mut rest = String::new();
+        let mut claimed_all = true;
+        for (sub_tag, sub_value) in split_tlv(value)? {
+            let path = ExtensionTag::SubTag(tag, sub_tag);
+            match self.parser(path) {
+                Some(parse) => extensions.insert_boxed(parse_at(parse, path, sub_value)?),
+                None => {
+                    claimed_all = false;
+                    write_tlv!(&mut rest, (sub_tag, sub_value)).map_err(Error::other)?;
+                }
+            }
+        }
+        Ok((!claimed_all).then_some(Cow::Owned(rest)))
+    }
+
+    fn parser(&self, tag: ExtensionTag) -> Option<Parser> {
+        self.parsers
+            .iter()
+            .find(|(registered, ..)| *registered == tag)
+            .map(|(.., parse)| *parse)
+    }
+}
+
+/// Writes template `tag` with the sub-tags of `base` followed by the extension sub-tags, which
+/// sort after every sub-tag the SDK models.
+pub(crate) fn write_template<W: Write + ?Sized, T: TLV>(
+    writer: &mut W,
+    tag: Tags,
+    base: Option<&T>,
+    extensions: &Extensions,
+) -> Result<(), Error> {
+    if extensions.first_sub_tag(tag.code()).is_none() {
+        return match base {
+            Some(base) => base.to_tlv(writer),
+            None => Ok(()),
+        };
+    }
+
+    let write_value = |writer: &mut dyn Write| -> Result<(), Error> {
+        if let Some(base) = base {
+            base.write_value(writer)?;
+        }
+        extensions.write_sub_fields(writer, tag.code())
+    };
+    let mut counter = LengthCounter::default();
+    write_value(&mut counter)?;
+    if counter.len() > tag.max_length() {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("{:?} exceeds max length {}", tag, tag.max_length()),
+        ));
+    }
+    write!(writer, "{:02}{:02}", tag.code(), counter.len()).map_err(Error::other)?;
+    write_value(&mut DynWriter(writer))
+}
+
+/// Writes one extension as `code`, length and value, measuring the value first.
+fn write_field<W: Write + ?Sized>(
+    writer: &mut W,
+    code: u8,
+    value: &dyn StoredExtension,
+) -> Result<(), Error> {
+    let mut counter = LengthCounter::default();
+    value.write_dyn(&mut counter)?;
+    if counter.len() > 99 {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("Extension {} exceeds max length 99", value.tag().path()),
+        ));
+    }
+    write!(writer, "{:02}{:02}", code, counter.len()).map_err(Error::other)?;
+    value.write_dyn(&mut DynWriter(writer))
+}
+
+/// Lends a possibly unsized writer out as `&mut dyn Write`.
+struct DynWriter<'a, W: ?Sized>(&'a mut W);
+
+impl<W: Write + ?Sized> Write for DynWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write_str(s)
+    }
+}
+
+fn parse_at(
+    parse: Parser,
+    tag: ExtensionTag,
+    value: &str,
+) -> Result<Box<dyn StoredExtension>, Error> {
+    parse(value).map_err(|e| Error::new(e.kind(), format!("Extension {}: {}", tag.path(), e)))
+}
+
+fn check_available(tag: ExtensionTag) -> Result<(), Error> {
+    if tag.is_available() {
+        return Ok(());
+    }
+    Err(Error::new(
+        ErrorKind::InvalidInput,
+        format!(
+            "Tag {} is modelled by the SDK and cannot be extended",
+            tag.path()
+        ),
+    ))
+}
@@ -0,0 +1,14 @@
+use alloc::format;
+use alloc::string::String;
+
+/// Lower-case hex MD5 of a payload, as used by Bakong to look up transactions.
+pub fn md5_hash(qr: &str) -> String {
+    format!("{:x}", md5::compute(qr.as_bytes()))
+}
+
+/// First eight characters of [`md5_hash`].
+pub fn short_hash(qr: &str) -> String {
+    let mut hash = md5_hash(qr);
+    hash.truncate(8);
+    hash
+}
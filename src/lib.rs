@@ -1,6 +1,56 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "capi")]
+mod capi;
+#[cfg(feature = "config")]
+mod config;
+mod diff;
+mod error;
+mod extension;
+mod hash;
 mod helper;
 mod models;
+#[cfg(all(feature = "node", not(test)))]
+mod node;
+mod payload;
+#[cfg(feature = "python")]
+mod python;
+#[cfg(feature = "svg")]
+mod render;
+mod scheme;
+#[cfg(feature = "server")]
+mod server;
 mod tlv;
+mod validation;
+#[cfg(feature = "wasm")]
+mod wasm;
 
+#[cfg(feature = "config")]
+pub use crate::config::{MerchantConfig, MerchantProfile};
+#[cfg(feature = "std")]
+pub use crate::diff::diff;
+pub use crate::diff::{Change, PayloadDiff, diff_at};
+pub use crate::error::{Error, ErrorKind};
+pub use crate::extension::{ExtensionRegistry, ExtensionTag, Extensions, TagExtension};
+pub use crate::hash::{md5_hash, short_hash};
 pub use crate::models::*;
+pub use crate::payload::{Payload, normalize};
+#[cfg(feature = "png")]
+pub use crate::render::render_png;
+#[cfg(feature = "svg")]
+pub use crate::render::{MAX_IMAGE_SIZE, render_svg};
+#[cfg(feature = "std")]
+pub use crate::scheme::decode_any;
+pub use crate::scheme::{
+    Confidence, DecodedQr, Scheme, SchemeDetection, decode_any_at, detect_scheme,
+};
+#[cfg(feature = "server")]
+pub use crate::server::{
+    HealthResponse, QrImage, QrPayload, QrRequest, QrResponse, ValidateResponse, router,
+};
 pub use crate::tlv::*;
+#[cfg(feature = "std")]
+pub use crate::validation::validate;
+pub use crate::validation::{Finding, Severity, validate_at};
@@ -1,19 +1,37 @@
+use crate::error::{Error, ErrorKind};
 use crate::models::{SubTags, Tags};
-use crate::tlv::TLV;
+use crate::tlv::{SeenTags, split_tlv};
+use crate::tlv::{SliceWriter, TLV};
+use crate::write_tlv;
+use alloc::format;
+use alloc::string::ToString;
 use chrono::{DateTime, TimeZone, Utc};
-use std::fmt::Write;
-use std::io::{Error, ErrorKind};
-
-#[derive(Debug)]
+use core::fmt::Write;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(
+    feature = "python",
+    pyo3::pyclass(get_all, set_all, eq, from_py_object)
+)]
 pub struct AdditionalDataField {
+    #[cfg_attr(feature = "wasm", tsify(type = "string"))]
     pub creation_timestamp: DateTime<Utc>,
+    #[cfg_attr(feature = "wasm", tsify(type = "string"))]
     pub expiration_timestamp: DateTime<Utc>,
 }
 
 impl AdditionalDataField {
+    #[cfg(feature = "std")]
     pub fn new(expiration: DateTime<Utc>) -> Result<Self, Error> {
-        let creation = Utc::now();
+        Self::with_creation(Utc::now(), expiration)
+    }
 
+    pub fn with_creation(
+        creation: DateTime<Utc>,
+        expiration: DateTime<Utc>,
+    ) -> Result<Self, Error> {
         if expiration < creation {
             return Err(Error::new(
                 ErrorKind::InvalidData,
@@ -37,26 +55,26 @@ impl AdditionalDataField {
         })
     }
 
+    #[cfg(feature = "std")]
     pub fn from_string(value: &str) -> Result<Self, Error> {
-        let mut slice = value;
+        Self::from_string_at(value, Utc::now())
+    }
+
+    /// Parses tag 99, treating `now` as the current time for the expiry check.
+    pub fn from_string_at(value: &str, now: DateTime<Utc>) -> Result<Self, Error> {
         let mut creation: Option<DateTime<Utc>> = None;
         let mut expiration: Option<DateTime<Utc>> = None;
 
-        while !slice.is_empty() {
-            let tag: u8 = slice[0..2]
-                .parse()
-                .map_err(|_| Error::new(ErrorKind::InvalidData, "Invalid sub-tag"))?;
-
-            let length: usize = slice[2..4]
-                .parse()
-                .map_err(|_| Error::new(ErrorKind::InvalidData, "Invalid length"))?;
+        let mut seen = SeenTags::default();
 
-            let end = 4 + length;
-            if end > slice.len() {
-                return Err(Error::new(ErrorKind::InvalidData, "Length error"));
+        for (tag, data) in split_tlv(value)? {
+            if !seen.insert(tag) {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Duplicate AdditionalDataField sub-tag {}", tag),
+                ));
             }
 
-            let data = &slice[4..end];
             let sub_tag = SubTags::from_code(Tags::AdditionalDataField, tag).ok_or_else(|| {
                 Error::new(
                     ErrorKind::InvalidData,
@@ -65,7 +83,7 @@ impl AdditionalDataField {
             })?;
 
             sub_tag
-                .validate_length(&data)
+                .validate_length(data)
                 .map_err(|msg| Error::new(ErrorKind::InvalidData, msg))?;
 
             match sub_tag {
@@ -89,8 +107,6 @@ impl AdditionalDataField {
                 }
                 _ => {}
             }
-
-            slice = &slice[end..];
         }
 
         let creation = creation
@@ -105,7 +121,7 @@ impl AdditionalDataField {
             ));
         }
 
-        if expiration < Utc::now() {
+        if expiration < now {
             return Err(Error::new(
                 ErrorKind::InvalidData,
                 "Expiration timestamp is already in the past",
@@ -120,42 +136,32 @@ impl AdditionalDataField {
 }
 
 impl TLV for AdditionalDataField {
-    fn to_tlv(&self, temp: &mut String, buffer: &mut String) -> Result<(), Error> {
-        let creation_str = self.creation_timestamp.timestamp_millis().to_string();
-        SubTags::CreationTimestamp
-            .validate_length(&creation_str)
-            .map_err(|msg| Error::new(ErrorKind::InvalidData, msg))?;
-        write!(
-            temp,
-            "{:02}{:02}{}",
-            SubTags::CreationTimestamp.code(),
-            creation_str.len(),
-            creation_str
-        )
-        .map_err(|e| Error::new(ErrorKind::Other, e))?;
-
-        let expiration_str = self.expiration_timestamp.timestamp_millis().to_string();
-        SubTags::ExpirationTimestamp
-            .validate_length(&expiration_str)
-            .map_err(|msg| Error::new(ErrorKind::InvalidData, msg))?;
-        write!(
-            temp,
-            "{:02}{:02}{}",
-            SubTags::ExpirationTimestamp.code(),
-            expiration_str.len(),
-            expiration_str
-        )
-        .map_err(|e| Error::new(ErrorKind::Other, e))?;
-
-        write!(
-            buffer,
-            "{:02}{:02}{}",
-            Tags::AdditionalDataField.code(),
-            temp.len(),
-            temp
-        )
-        .map_err(|e| Error::new(ErrorKind::Other, e))?;
-        temp.clear();
+    fn tag(&self) -> Tags {
+        Tags::AdditionalDataField
+    }
+
+    fn write_value<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        #[inline]
+        fn write_timestamp<W: Write + ?Sized>(
+            writer: &mut W,
+            sub_tag: SubTags,
+            timestamp: &DateTime<Utc>,
+        ) -> Result<(), Error> {
+            let mut buffer = [0u8; 20];
+            let mut millis = SliceWriter::new(&mut buffer);
+            write!(millis, "{}", timestamp.timestamp_millis()).map_err(Error::other)?;
+            sub_tag
+                .validate_length(millis.as_str())
+                .map_err(|msg| Error::new(ErrorKind::InvalidData, msg))?;
+            write_tlv!(writer, (sub_tag.code(), millis.as_str())).map_err(Error::other)
+        }
+
+        write_timestamp(writer, SubTags::CreationTimestamp, &self.creation_timestamp)?;
+        write_timestamp(
+            writer,
+            SubTags::ExpirationTimestamp,
+            &self.expiration_timestamp,
+        )?;
         Ok(())
     }
 }
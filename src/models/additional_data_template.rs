@@ -1,11 +1,21 @@
+use crate::error::{Error, ErrorKind};
+use crate::tlv::{SeenTags, split_tlv};
 use crate::{
     TLV,
     models::{SubTags, Tags},
+    write_tlv,
 };
-use std::fmt::Write;
-use std::io::{Error, ErrorKind};
-
-#[derive(Debug)]
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt::Write;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(
+    feature = "python",
+    pyo3::pyclass(get_all, set_all, eq, from_py_object)
+)]
 pub struct AdditionalDataTemplate {
     pub bill_number: Option<String>,
     pub store_label: Option<String>,
@@ -30,7 +40,7 @@ impl AdditionalDataTemplate {
         }
     }
 
-    pub fn from_string(value: &str) -> std::io::Result<Self> {
+    pub fn from_string(value: &str) -> Result<Self, Error> {
         let mut additional_data_template = AdditionalDataTemplate {
             bill_number: None,
             store_label: None,
@@ -39,31 +49,16 @@ impl AdditionalDataTemplate {
             purpose_of_transaction: None,
         };
 
-        let mut slice = value;
-
-        while !slice.is_empty() {
-            let tag: u8 = slice[0..2].parse().map_err(|_| {
-                Error::new(
-                    ErrorKind::InvalidData,
-                    format!("Tag {} can't be parsed", &slice[0..2]),
-                )
-            })?;
+        let mut seen = SeenTags::default();
 
-            let length: usize = slice[2..4].parse().map_err(|_| {
-                Error::new(
+        for (tag, data_value) in split_tlv(value)? {
+            if !seen.insert(tag) {
+                return Err(Error::new(
                     ErrorKind::InvalidData,
-                    format!("Error parsing length for tag {}", tag),
-                )
-            })?;
-
-            let value_end = 4 + length;
-
-            if value_end > slice.len() {
-                return Err(Error::new(ErrorKind::InvalidData, "Length is invalid"));
+                    format!("Duplicate AdditionalDataTemplate sub-tag {}", tag),
+                ));
             }
 
-            let data_value: &str = &slice[4..value_end];
-
             let sub_tag =
                 SubTags::from_code(Tags::AdditionalDataTemplate, tag).ok_or_else(|| {
                     Error::new(
@@ -77,8 +72,6 @@ impl AdditionalDataTemplate {
                 .map_err(|msg| Error::new(ErrorKind::InvalidData, msg))?;
 
             additional_data_template.apply_sub_tag(&sub_tag, data_value);
-
-            slice = &slice[value_end..];
         }
 
         Ok(additional_data_template)
@@ -86,42 +79,35 @@ impl AdditionalDataTemplate {
 }
 
 impl TLV for AdditionalDataTemplate {
-    fn to_tlv(&self, temp: &mut String, buffer: &mut String) -> Result<(), std::io::Error> {
+    fn tag(&self) -> Tags {
+        Tags::AdditionalDataTemplate
+    }
+
+    fn write_value<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
         #[inline]
-        fn write_field(
-            temp: &mut String,
+        fn write_field<W: Write + ?Sized>(
+            writer: &mut W,
             sub_tag: SubTags,
             value: &Option<String>,
-        ) -> Result<(), std::io::Error> {
+        ) -> Result<(), Error> {
             if let Some(val) = value {
                 sub_tag
                     .validate_length(val)
                     .map_err(|msg| Error::new(ErrorKind::InvalidData, msg))?;
-                write!(temp, "{:02}{:02}{}", sub_tag.code(), val.len(), val)
-                    .map_err(|e| Error::new(ErrorKind::Other, e))?;
+                write_tlv!(writer, (sub_tag.code(), val)).map_err(Error::other)?;
             }
             Ok(())
         }
 
-        write_field(temp, SubTags::BillNumber, &self.bill_number)?;
-        write_field(temp, SubTags::MobileNumber, &self.mobile_number)?;
-        write_field(temp, SubTags::StoreLabel, &self.store_label)?;
-        write_field(temp, SubTags::TerminalLabel, &self.terminal_label)?;
+        write_field(writer, SubTags::BillNumber, &self.bill_number)?;
+        write_field(writer, SubTags::MobileNumber, &self.mobile_number)?;
+        write_field(writer, SubTags::StoreLabel, &self.store_label)?;
+        write_field(writer, SubTags::TerminalLabel, &self.terminal_label)?;
         write_field(
-            temp,
+            writer,
             SubTags::PurposeOfTransaction,
             &self.purpose_of_transaction,
         )?;
-
-        write!(
-            buffer,
-            "{:02}{:02}{}",
-            Tags::AdditionalDataTemplate.code(),
-            temp.len(),
-            temp
-        )
-        .map_err(|e| Error::new(ErrorKind::Other, e))?;
-        temp.clear();
         Ok(())
     }
 }
@@ -1,27 +1,96 @@
 use crate::TLV;
+use crate::error::{Error, ErrorKind};
+use crate::extension::{ExtensionRegistry, Extensions, TagExtension, write_template};
 use crate::models::{
-    AdditionalDataField, AdditionalDataTemplate, CountryCode, IndivualInformation, MerchantCity,
-    MerchantInformation, MerchantInformationLanguageTemplate, MerchantType, PointOfInitialMethod,
-    Tags, TransactionAmount, TransactionCurrency, crc16_ccitt,
+    AdditionalDataField, AdditionalDataTemplate, CountryCode, Crc16Ccitt, IndivualInformation,
+    MerchantAccountTemplate, MerchantCity, MerchantInformation,
+    MerchantInformationLanguageTemplate, MerchantLocation, MerchantType, MergedTemplate,
+    NetworkMerchantAccount, PointOfInitialMethod, SubTags, Tags, TipOrConvenienceFee,
+    TransactionAmount, TransactionCurrency, UnionPayMerchant, UnreservedTemplate,
+    validate_postal_code,
 };
+use crate::tlv::{CrcWriter, SeenTags, SliceWriter};
 use crate::write_tlv;
-use std::fmt::Write;
-use std::io::{Error, ErrorKind};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use chrono::{DateTime, Utc};
+use core::fmt::Write;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "wasm",
+    derive(tsify::Tsify),
+    tsify(into_wasm_abi, from_wasm_abi)
+)]
+#[cfg_attr(
+    feature = "python",
+    pyo3::pyclass(get_all, set_all, eq, from_py_object)
+)]
 pub struct Bakong {
     pub qr_type: PointOfInitialMethod,
     pub merchant_type: MerchantType,
     pub merchant_category_code: Option<String>,
+    /// Tags 55–57; a fixed fee must be in the QR's currency.
+    pub tip_or_convenience_fee: Option<TipOrConvenienceFee>,
     pub merchant_name: String,
     pub merchant_city: Option<MerchantCity>,
+    /// Tag 61; a Cambodian code is 6 digits starting with the province number.
+    pub postal_code: Option<String>,
+    /// Tag 58; generated as `KH` when unset.
+    pub country_code: Option<CountryCode>,
     pub additional_data_template: Option<AdditionalDataTemplate>,
-    pub unionpay_merchant: Option<String>,
+    pub unionpay_merchant: Option<UnionPayMerchant>,
+    /// Card network merchant accounts (tags 02–14 and 16–25), written in ascending tag order.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub network_merchant_accounts: Vec<NetworkMerchantAccount>,
+    /// Other schemes' merchant account templates (tags 26–28 and 31–51), written back verbatim
+    /// in ascending tag order.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub merchant_account_templates: Vec<MerchantAccountTemplate>,
     pub merchant_information_language_template: Option<MerchantInformationLanguageTemplate>,
+    /// Unreserved templates (tags 80–98), written in ascending tag order.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unreserved_templates: Vec<UnreservedTemplate>,
+    /// Values of registered `TagExtension`s; see `ExtensionRegistry`. Skipped by serde, so the
+    /// JSON, wasm and node bindings never carry them: keep the QR string to round-trip them.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub extensions: Extensions,
 }
 
 impl Bakong {
-    pub fn decode_qr(qr: &str) -> Result<Bakong, std::io::Error> {
+    #[cfg(feature = "std")]
+    pub fn decode_qr(qr: &str) -> Result<Bakong, Error> {
+        Self::decode_qr_at(qr, Utc::now())
+    }
+
+    /// Decodes `qr`, treating `now` as the current time when checking the tag 99 expiry.
+    pub fn decode_qr_at(qr: &str, now: DateTime<Utc>) -> Result<Bakong, Error> {
+        Self::decode_qr_with(qr, now, &ExtensionRegistry::new())
+    }
+
+    pub(crate) fn decode_qr_with(
+        qr: &str,
+        now: DateTime<Utc>,
+        registry: &ExtensionRegistry,
+    ) -> Result<Bakong, Error> {
+        let bakong = Self::decode_fields(qr, now, registry)?;
+        if !Crc16Ccitt::verify(qr) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "CRC is missing or does not match the payload",
+            ));
+        }
+        Ok(bakong)
+    }
+
+    /// Decodes every field of `qr` without checking its CRC, which `validate` reports apart.
+    pub(crate) fn decode_fields(
+        qr: &str,
+        now: DateTime<Utc>,
+        registry: &ExtensionRegistry,
+    ) -> Result<Bakong, Error> {
         if qr.len() > 256 {
             return Err(Error::new(
                 ErrorKind::InvalidInput,
@@ -29,27 +98,51 @@ impl Bakong {
             ));
         }
 
+        if !qr.starts_with("0002") {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Payload must start with the payload format indicator (tag 00)",
+            ));
+        }
+
         let mut slice = qr;
 
         let mut is_static: Option<bool> = None;
         let mut currency: Option<TransactionCurrency> = None;
         let mut amount_raw: Option<String> = None;
+        let mut tip_indicator: Option<&str> = None;
+        let mut fee_fixed: Option<&str> = None;
+        let mut fee_percentage: Option<&str> = None;
         let mut merchant_category_code = None;
         let mut merchant_name = String::new();
         let mut merchant_city: Option<MerchantCity> = None;
+        let mut postal_code: Option<String> = None;
+        let mut country_code: Option<CountryCode> = None;
         let mut additional_data_template: Option<AdditionalDataTemplate> = None;
         let mut additional_data_field: Option<AdditionalDataField> = None;
-        let mut unionpay_merchant: Option<String> = None;
+        let mut unionpay_merchant: Option<UnionPayMerchant> = None;
+        let mut network_merchant_accounts = Vec::new();
+        let mut merchant_account_templates = Vec::new();
         let mut merchant_information_language_template: Option<
             MerchantInformationLanguageTemplate,
         > = None;
+        let mut unreserved_templates = Vec::new();
+        let mut extensions = Extensions::default();
         let mut merchant_type: Option<MerchantType> = None;
+        let mut seen = SeenTags::default();
 
         while slice.len() >= 4 {
-            let raw_tag: u8 = slice[0..2].parse().map_err(|_| {
+            // `get` rather than indexing: a multi-byte character must fail, not panic.
+            let (Some(code), Some(raw_length)) = (slice.get(0..2), slice.get(2..4)) else {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Invalid TLV '{}'", slice),
+                ));
+            };
+            let raw_tag: u8 = code.parse().map_err(|_| {
                 Error::new(
                     ErrorKind::InvalidData,
-                    format!("Invalid tag code '{}'", &slice[0..2]),
+                    format!("Invalid tag code '{}'", code),
                 )
             })?;
 
@@ -57,33 +150,65 @@ impl Bakong {
                 Error::new(ErrorKind::InvalidData, format!("Unknown tag '{}'", raw_tag))
             })?;
 
-            let length: usize = slice[2..4].parse().map_err(|_| {
+            if !seen.insert(raw_tag) {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Duplicate tag '{}'", raw_tag),
+                ));
+            }
+
+            let length: usize = raw_length.parse().map_err(|_| {
                 Error::new(
                     ErrorKind::InvalidData,
-                    format!("Invalid length '{}'", &slice[2..4]),
+                    format!("Invalid length '{}'", raw_length),
                 )
             })?;
 
             let end = 4 + length;
-            if end > slice.len() {
-                return Err(Error::new(
+            let value = slice.get(4..end).ok_or_else(|| {
+                Error::new(
                     ErrorKind::InvalidData,
                     "Declared length exceeds remaining slice",
-                ));
-            }
+                )
+            })?;
 
-            let value = &slice[4..end];
+            if registry.decode_tag(raw_tag, value, &mut extensions)? {
+                slice = &slice[end..];
+                continue;
+            }
 
             match tag {
+                Tags::PayloadFormatIndicator if value != "01" => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!("Unsupported payload format indicator '{}'", value),
+                    ));
+                }
                 Tags::PointOfInitialMethod => {
-                    is_static = PointOfInitialMethod::is_static_from_string(value);
+                    is_static = Some(
+                        PointOfInitialMethod::is_static_from_string(value).ok_or_else(|| {
+                            Error::new(
+                                ErrorKind::InvalidData,
+                                format!("Invalid point of initiation method '{}' (tag 01)", value),
+                            )
+                        })?,
+                    );
                 }
                 Tags::TransactionCurrency => {
-                    currency = Some(TransactionCurrency::from_string(value)?);
+                    currency = Some(bakong_currency(TransactionCurrency::from_string(value)?)?);
                 }
                 Tags::TransactionAmount => {
                     amount_raw = Some(value.to_string());
                 }
+                Tags::TipOrConvenienceIndicator => {
+                    tip_indicator = Some(value);
+                }
+                Tags::ConvenienceFeeFixed => {
+                    fee_fixed = Some(value);
+                }
+                Tags::ConvenienceFeePercentage => {
+                    fee_percentage = Some(value);
+                }
                 Tags::MerchantName => {
                     merchant_name = value.to_string();
                 }
@@ -93,19 +218,50 @@ impl Bakong {
                 Tags::MerchantCity => {
                     merchant_city = MerchantCity::from_string(value);
                 }
+                Tags::PostalCode => {
+                    postal_code = Some(value.to_string());
+                }
+                Tags::CountryCode => {
+                    country_code = Some(CountryCode::from_code(value).ok_or_else(|| {
+                        Error::new(
+                            ErrorKind::InvalidData,
+                            format!("Unsupported country code '{}'", value),
+                        )
+                    })?);
+                }
                 Tags::AdditionalDataTemplate => {
-                    additional_data_template = Some(AdditionalDataTemplate::from_string(value)?);
+                    if let Some(value) =
+                        registry.decode_sub_fields(raw_tag, value, &mut extensions)?
+                    {
+                        additional_data_template =
+                            Some(AdditionalDataTemplate::from_string(&value)?);
+                    }
                 }
                 Tags::AdditionalDataField => {
-                    additional_data_field = Some(AdditionalDataField::from_string(value)?);
+                    let value = registry.decode_sub_fields(raw_tag, value, &mut extensions)?;
+                    additional_data_field = Some(AdditionalDataField::from_string_at(
+                        value.as_deref().unwrap_or_default(),
+                        now,
+                    )?);
                 }
                 Tags::UnionPayMerchant => {
-                    unionpay_merchant = Some(value.to_string());
+                    unionpay_merchant = Some(UnionPayMerchant::from_string(value));
+                }
+                Tags::NetworkMerchantAccount(code) => {
+                    network_merchant_accounts
+                        .push(NetworkMerchantAccount::new(code, value.to_string())?);
                 }
                 Tags::MerchantInformationLanguageTemplate => {
                     merchant_information_language_template =
                         Some(MerchantInformationLanguageTemplate::from_string(value)?);
                 }
+                Tags::UnreservedTemplate(code) => {
+                    let value = registry.decode_sub_fields(code, value, &mut extensions)?;
+                    unreserved_templates.push(UnreservedTemplate::from_string(
+                        code,
+                        value.as_deref().unwrap_or_default(),
+                    )?);
+                }
                 Tags::MerchantAccountInfoIndividual => {
                     if merchant_type.is_some() {
                         return Err(Error::new(
@@ -116,6 +272,10 @@ impl Bakong {
                     let individual = IndivualInformation::from_string(value)?;
                     merchant_type = Some(MerchantType::Indivual(individual));
                 }
+                Tags::MerchantAccountInformation(code) => {
+                    merchant_account_templates
+                        .push(MerchantAccountTemplate::new(code, value.to_string())?);
+                }
                 Tags::MerchantAccountInfoMerchant => {
                     if merchant_type.is_some() {
                         return Err(Error::new(
@@ -132,14 +292,33 @@ impl Bakong {
             slice = &slice[end..];
         }
 
+        if is_static == Some(true) {
+            if amount_raw.is_some() {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "Static QR must not carry a transaction amount (tag 54)",
+                ));
+            }
+            if additional_data_field.is_some() {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "Static QR must not carry an additional data field (tag 99)",
+                ));
+            }
+        }
+
+        // Without tag 01 the QR is dynamic exactly when it carries an amount or tag 99.
+        let is_static =
+            is_static.unwrap_or(amount_raw.is_none() && additional_data_field.is_none());
+
         let qr_type = match is_static {
-            Some(true) => {
+            true => {
                 let currency = currency.ok_or_else(|| {
                     Error::new(ErrorKind::InvalidData, "Missing currency for static QR")
                 })?;
                 PointOfInitialMethod::Static(currency)
             }
-            Some(false) => {
+            false => {
                 let currency = currency.ok_or_else(|| {
                     Error::new(ErrorKind::InvalidData, "Missing currency for dynamic QR")
                 })?;
@@ -152,9 +331,15 @@ impl Bakong {
                     amount,
                 }
             }
-            None => PointOfInitialMethod::Static(TransactionCurrency::KHR),
         };
 
+        let tip_or_convenience_fee = TipOrConvenienceFee::from_tags(
+            tip_indicator,
+            fee_fixed,
+            fee_percentage,
+            &qr_type.currency(),
+        )?;
+
         let merchant_type = merchant_type.ok_or_else(|| {
             Error::new(
                 ErrorKind::InvalidData,
@@ -162,37 +347,229 @@ impl Bakong {
             )
         })?;
 
+        let country_code = country_code
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "Missing country code (tag 58)"))?;
+
+        if let Some(postal_code) = &postal_code {
+            validate_postal_code(postal_code, &country_code)?;
+        }
+
         Ok(Bakong {
             qr_type,
             merchant_type,
             merchant_category_code,
+            tip_or_convenience_fee,
             merchant_name,
             merchant_city,
+            postal_code,
+            country_code: Some(country_code),
             additional_data_template,
             unionpay_merchant,
+            network_merchant_accounts,
+            merchant_account_templates,
             merchant_information_language_template,
+            unreserved_templates,
+            extensions,
         })
     }
 
-    pub fn generate_qr(&self) -> Result<String, std::io::Error> {
+    /// Turns a static QR into a dynamic one for `amount`, keeping the merchant account, city,
+    /// category code and language template. `amount` must be in the static QR's currency.
+    pub fn to_dynamic(
+        &self,
+        amount: TransactionAmount,
+        bill_number: Option<String>,
+        additional_data_field: Option<AdditionalDataField>,
+    ) -> Result<Bakong, Error> {
+        let PointOfInitialMethod::Static(currency) = &self.qr_type else {
+            return Err(Error::new(ErrorKind::InvalidInput, "QR is already dynamic"));
+        };
+
+        if amount.currency() != *currency {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Amount currency {:?} does not match the static QR currency {:?}",
+                    amount.currency(),
+                    currency
+                ),
+            ));
+        }
+
+        let mut dynamic = self.clone();
+        if let Some(bill_number) = bill_number {
+            SubTags::BillNumber
+                .validate_length(&bill_number)
+                .map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+            dynamic
+                .additional_data_template
+                .get_or_insert(AdditionalDataTemplate {
+                    bill_number: None,
+                    store_label: None,
+                    terminal_label: None,
+                    mobile_number: None,
+                    purpose_of_transaction: None,
+                })
+                .bill_number = Some(bill_number);
+        }
+        dynamic.qr_type = PointOfInitialMethod::Dynamic {
+            additional_data_field,
+            amount,
+        };
+
+        Ok(dynamic)
+    }
+
+    /// Recovers the static QR behind a dynamic one by dropping the amount, tag 99 (with any
+    /// extension sub-tags in it) and bill number. Static QRs are returned unchanged.
+    pub fn to_static(&self) -> Bakong {
+        let PointOfInitialMethod::Dynamic { amount, .. } = &self.qr_type else {
+            return self.clone();
+        };
+
+        let mut fixed = self.clone();
+        fixed.qr_type = PointOfInitialMethod::Static(amount.currency());
+        fixed
+            .extensions
+            .remove_sub_fields(Tags::AdditionalDataField.code());
+        if let Some(template) = &mut fixed.additional_data_template {
+            template.bill_number = None;
+            if template.store_label.is_none()
+                && template.terminal_label.is_none()
+                && template.mobile_number.is_none()
+                && template.purpose_of_transaction.is_none()
+            {
+                fixed.additional_data_template = None;
+            }
+        }
+
+        fixed
+    }
+
+    /// The stored value of extension `T`.
+    pub fn extension<T: TagExtension>(&self) -> Option<&T> {
+        self.extensions.get::<T>()
+    }
+
+    /// The unreserved template (tags 80–98) carrying `guid`, compared case-insensitively.
+    pub fn unreserved_template(&self, guid: &str) -> Option<&UnreservedTemplate> {
+        self.unreserved_templates
+            .iter()
+            .find(|template| template.guid.eq_ignore_ascii_case(guid))
+    }
+
+    /// Province from tag 60, refined with the district encoded in a Cambodian postal code.
+    pub fn location(&self) -> Option<MerchantLocation> {
+        let from_postal_code = self
+            .postal_code
+            .as_deref()
+            .and_then(|code| MerchantLocation::from_postal_code(code).ok());
+
+        match (&self.merchant_city, from_postal_code) {
+            (Some(city), Some(mut location))
+                if city.province_number() == location.province.province_number() =>
+            {
+                location.province = city.clone();
+                Some(location)
+            }
+            (Some(city), _) => Some(MerchantLocation {
+                province: city.clone(),
+                district: None,
+                commune: None,
+            }),
+            (None, location) => location,
+        }
+    }
+
+    pub fn generate_qr(&self) -> Result<String, Error> {
         let mut qr_code = String::with_capacity(255);
-        let mut temp_value = String::with_capacity(99);
+        self.write_qr(&mut qr_code)?;
+        Ok(qr_code)
+    }
+
+    pub fn write_qr_to_slice<'a>(&self, buffer: &'a mut [u8]) -> Result<&'a str, Error> {
+        let mut writer = SliceWriter::new(buffer);
+        self.write_qr(&mut writer).map_err(|e| match e.kind() {
+            ErrorKind::Other => Error::new(
+                ErrorKind::InvalidInput,
+                "Buffer is too small for the generated QR",
+            ),
+            _ => e,
+        })?;
+        Ok(writer.into_str())
+    }
+
+    /// Encodes the payload in canonical order: ascending tags with tag 63 last.
+    pub fn write_qr<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        let mut qr_code = CrcWriter::new(writer);
         let mut dynamic_additional: Option<&AdditionalDataField> = None;
+        self.extensions.check_conflicts()?;
 
         Tags::PayloadFormatIndicator
             .validate_length("01")
             .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
         write_tlv!(&mut qr_code, (Tags::PayloadFormatIndicator.code(), "01"))
-            .map_err(|e| Error::new(ErrorKind::Other, e))?;
+            .map_err(Error::other)?;
 
         let poi_value = self.qr_type.value();
         Tags::PointOfInitialMethod
             .validate_length(poi_value)
             .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
         write_tlv!(&mut qr_code, (Tags::PointOfInitialMethod.code(), poi_value))
-            .map_err(|e| Error::new(ErrorKind::Other, e))?;
+            .map_err(Error::other)?;
 
-        self.merchant_type.to_tlv(&mut temp_value, &mut qr_code)?;
+        for account in &self.network_merchant_accounts {
+            account.validate()?;
+        }
+        for tag in 2..=25 {
+            if tag == Tags::UnionPayMerchant.code() {
+                if let Some(unionpay) = &self.unionpay_merchant {
+                    unionpay.to_tlv(&mut qr_code)?;
+                }
+                continue;
+            }
+
+            let mut accounts = self
+                .network_merchant_accounts
+                .iter()
+                .filter(|account| account.tag == tag);
+            if let Some(account) = accounts.next() {
+                if accounts.next().is_some() {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!("Duplicate network merchant account {:02}", tag),
+                    ));
+                }
+                account.to_tlv(&mut qr_code)?;
+            }
+        }
+
+        for template in &self.merchant_account_templates {
+            template.validate()?;
+        }
+        let merchant_account_tag = self.merchant_type.tag().code();
+        for tag in 26..=51 {
+            if tag == merchant_account_tag {
+                self.merchant_type.to_tlv(&mut qr_code)?;
+                continue;
+            }
+
+            let mut templates = self
+                .merchant_account_templates
+                .iter()
+                .filter(|template| template.tag == tag);
+            let template = templates.next();
+            if templates.next().is_some() || (template.is_some() && self.extensions.owns_tag(tag)) {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Duplicate merchant account template {:02}", tag),
+                ));
+            }
+            match template {
+                Some(template) => template.to_tlv(&mut qr_code)?,
+                None => self.extensions.write_tag(&mut qr_code, tag)?,
+            }
+        }
 
         if let Some(merchant_category_code) = &self.merchant_category_code {
             Tags::MerchantCategoryCode
@@ -202,34 +579,31 @@ impl Bakong {
                 &mut qr_code,
                 (Tags::MerchantCategoryCode.code(), merchant_category_code)
             )
-            .map_err(|e| Error::new(ErrorKind::Other, e))?;
+            .map_err(Error::other)?;
         }
 
+        bakong_currency(self.qr_type.currency())?;
         self.qr_type.write_currency(&mut qr_code)?;
 
-        if let Some(unionpay) = &self.unionpay_merchant {
-            Tags::UnionPayMerchant
-                .validate_length(unionpay)
-                .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
-            write_tlv!(&mut qr_code, (Tags::UnionPayMerchant.code(), unionpay))
-                .map_err(|e| Error::new(ErrorKind::Other, e))?;
-        }
-
         if let PointOfInitialMethod::Dynamic {
             additional_data_field,
             amount,
         } = &self.qr_type
         {
             dynamic_additional = additional_data_field.as_ref();
-            amount.write_amount(&mut temp_value, &mut qr_code)?;
+            amount.write_amount(&mut qr_code)?;
         }
 
-        let country_code = CountryCode::KH.code();
+        if let Some(tip_or_convenience_fee) = &self.tip_or_convenience_fee {
+            tip_or_convenience_fee.write_tags(&mut qr_code, &self.qr_type.currency())?;
+        }
+
+        let country = self.country_code.as_ref().unwrap_or(&CountryCode::KH);
+        let country_code = country.code();
         Tags::CountryCode
             .validate_length(country_code)
             .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
-        write_tlv!(&mut qr_code, (Tags::CountryCode.code(), country_code))
-            .map_err(|e| Error::new(ErrorKind::Other, e))?;
+        write_tlv!(&mut qr_code, (Tags::CountryCode.code(), country_code)).map_err(Error::other)?;
 
         Tags::MerchantName
             .validate_length(&self.merchant_name)
@@ -238,7 +612,7 @@ impl Bakong {
             &mut qr_code,
             (Tags::MerchantName.code(), &self.merchant_name)
         )
-        .map_err(|e| Error::new(ErrorKind::Other, e))?;
+        .map_err(Error::other)?;
 
         let merchant_city = self
             .merchant_city
@@ -248,26 +622,108 @@ impl Bakong {
         Tags::MerchantCity
             .validate_length(city_value)
             .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
-        write_tlv!(&mut qr_code, (Tags::MerchantCity.code(), city_value))
-            .map_err(|e| Error::new(ErrorKind::Other, e))?;
+        write_tlv!(&mut qr_code, (Tags::MerchantCity.code(), city_value)).map_err(Error::other)?;
 
-        if let Some(additional_data_template) = &self.additional_data_template {
-            additional_data_template.to_tlv(&mut temp_value, &mut qr_code)?;
+        if let Some(postal_code) = &self.postal_code {
+            validate_postal_code(postal_code, country)?;
+            write_tlv!(&mut qr_code, (Tags::PostalCode.code(), postal_code))
+                .map_err(Error::other)?;
         }
 
+        write_template(
+            &mut qr_code,
+            Tags::AdditionalDataTemplate,
+            self.additional_data_template.as_ref(),
+            &self.extensions,
+        )?;
+
         if let Some(merchant_information) = &self.merchant_information_language_template {
-            merchant_information.to_tlv(&mut temp_value, &mut qr_code)?;
+            merchant_information.to_tlv(&mut qr_code)?;
+        }
+
+        for tag in 80..=98 {
+            self.write_unreserved_template(&mut qr_code, tag)?;
+        }
+
+        if dynamic_additional.is_none()
+            && let Some(sub_tag) = self
+                .extensions
+                .first_sub_tag(Tags::AdditionalDataField.code())
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Extension 99.{:02} requires tag 99 on a dynamic QR",
+                    sub_tag
+                ),
+            ));
         }
+        write_template(
+            &mut qr_code,
+            Tags::AdditionalDataField,
+            dynamic_additional,
+            &self.extensions,
+        )?;
 
-        if let Some(additional_data) = dynamic_additional {
-            additional_data.to_tlv(&mut temp_value, &mut qr_code)?;
+        qr_code.finish()
+    }
+
+    /// Writes unreserved template `tag` from `unreserved_templates` with any extension sub-tags
+    /// merged in, or the extension that owns the whole tag.
+    fn write_unreserved_template<W: Write + ?Sized>(
+        &self,
+        writer: &mut W,
+        tag: u8,
+    ) -> Result<(), Error> {
+        let mut templates = self
+            .unreserved_templates
+            .iter()
+            .filter(|template| template.tag == tag);
+        let template = templates.next();
+        if templates.next().is_some() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("Duplicate unreserved template {:02}", tag),
+            ));
         }
+        let first_sub_tag = self.extensions.first_sub_tag(tag);
 
-        write!(&mut qr_code, "{:02}{:02}", Tags::Crc.code(), 4)
-            .map_err(|e| Error::new(ErrorKind::Other, e))?;
-        let crc = crc16_ccitt(&qr_code);
-        write!(&mut qr_code, "{:04X}", crc).map_err(|e| Error::new(ErrorKind::Other, e))?;
+        match (template, self.extensions.owns_tag(tag)) {
+            (Some(_), true) => Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Tag {:02} is both an unreserved template and an extension",
+                    tag
+                ),
+            )),
+            (Some(template), false) if first_sub_tag.is_none() => template.to_tlv(writer),
+            (Some(template), false) => MergedTemplate {
+                template,
+                extensions: &self.extensions,
+            }
+            .to_tlv(writer),
+            (None, true) => self.extensions.write_tag(writer, tag),
+            (None, false) => match first_sub_tag {
+                Some(sub_tag) => Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!(
+                        "Extension {:02}.{:02} requires an unreserved template with tag {:02}",
+                        tag, sub_tag, tag
+                    ),
+                )),
+                None => Ok(()),
+            },
+        }
+    }
+}
 
-        Ok(qr_code)
+/// KHQR only carries riel and dollar amounts.
+fn bakong_currency(currency: TransactionCurrency) -> Result<TransactionCurrency, Error> {
+    if !currency.is_bakong() {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("Bakong QR currency must be KHR or USD, got {:?}", currency),
+        ));
     }
+    Ok(currency)
 }
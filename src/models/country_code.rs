@@ -1,11 +1,62 @@
+/// ISO 3166-1 alpha-2 countries of the QR schemes the SDK meets in cross-border payments.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "python", pyo3::pyclass(eq, eq_int, from_py_object))]
 pub enum CountryCode {
     KH,
+    TH,
+    VN,
+    LA,
+    MY,
+    SG,
+    ID,
+    PH,
+    MM,
+    BN,
+    CN,
+    HK,
+    JP,
+    KR,
 }
 
 impl CountryCode {
     pub fn code(&self) -> &'static str {
         match self {
             CountryCode::KH => "KH",
+            CountryCode::TH => "TH",
+            CountryCode::VN => "VN",
+            CountryCode::LA => "LA",
+            CountryCode::MY => "MY",
+            CountryCode::SG => "SG",
+            CountryCode::ID => "ID",
+            CountryCode::PH => "PH",
+            CountryCode::MM => "MM",
+            CountryCode::BN => "BN",
+            CountryCode::CN => "CN",
+            CountryCode::HK => "HK",
+            CountryCode::JP => "JP",
+            CountryCode::KR => "KR",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "KH" => Some(CountryCode::KH),
+            "TH" => Some(CountryCode::TH),
+            "VN" => Some(CountryCode::VN),
+            "LA" => Some(CountryCode::LA),
+            "MY" => Some(CountryCode::MY),
+            "SG" => Some(CountryCode::SG),
+            "ID" => Some(CountryCode::ID),
+            "PH" => Some(CountryCode::PH),
+            "MM" => Some(CountryCode::MM),
+            "BN" => Some(CountryCode::BN),
+            "CN" => Some(CountryCode::CN),
+            "HK" => Some(CountryCode::HK),
+            "JP" => Some(CountryCode::JP),
+            "KR" => Some(CountryCode::KR),
+            _ => None,
         }
     }
 }
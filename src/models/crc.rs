@@ -1,6 +1,21 @@
-const fn generate_crc16_table() -> [u16; 256] {
-    const POLY: u16 = 0x1021;
-    let mut table = [0u16; 256];
+const POLY: u16 = 0x1021;
+const INIT: u16 = 0xFFFF;
+const SLICES: usize = 8;
+
+// CRC-16/CCITT-FALSE check value and the EMVCo MPM specification example payload.
+const _: () = assert!(crc16_ccitt_update(INIT, b"123456789") == 0x29B1);
+const _: () = assert!(
+    crc16_ccitt_update(
+        INIT,
+        "00020101021229300012D156000000000510A93FO3230Q31280012D15600000001030812345678520441115802CN5914BEST TRANSPORT6007BEIJING64200002ZH0104最佳运输0202北京540523.7253031565502016233030412340603***0708A60086670902ME91320016A0112233449988770708123456786304"
+            .as_bytes()
+    ) == 0xA13A
+);
+
+static CRC16_TABLES: [[u16; 256]; SLICES] = generate_crc16_tables();
+
+const fn generate_crc16_tables() -> [[u16; 256]; SLICES] {
+    let mut tables = [[0u16; 256]; SLICES];
     let mut i = 0;
     let mut b = 0;
     while i < 256 {
@@ -13,20 +28,95 @@ const fn generate_crc16_table() -> [u16; 256] {
             }
             b += 1;
         }
-        table[i as usize] = crc;
+        tables[0][i] = crc;
         i += 1;
         b = 0;
     }
-    table
+
+    // tables[k][x] is the CRC contribution of byte x followed by k zero bytes.
+    let mut k = 1;
+    while k < SLICES {
+        i = 0;
+        while i < 256 {
+            let prev = tables[k - 1][i];
+            tables[k][i] = (prev << 8) ^ tables[0][(prev >> 8) as usize];
+            i += 1;
+        }
+        k += 1;
+    }
+    tables
 }
 
 pub fn crc16_ccitt(data: &str) -> u16 {
-    let data_bytes = data.as_bytes();
-    let table = generate_crc16_table();
-    let mut crc: u16 = 0xFFFF;
-    for &byte in data_bytes {
-        let index = ((crc >> 8) ^ byte as u16) & 0xFF;
-        crc = (crc << 8) ^ table[index as usize];
+    crc16_ccitt_update(INIT, data.as_bytes())
+}
+
+pub const fn crc16_ccitt_update(crc: u16, data: &[u8]) -> u16 {
+    let tables = &CRC16_TABLES;
+    let mut crc = crc;
+    let mut i = 0;
+
+    while i + SLICES <= data.len() {
+        let hi = (data[i] ^ (crc >> 8) as u8) as usize;
+        let lo = (data[i + 1] ^ crc as u8) as usize;
+        crc = tables[7][hi]
+            ^ tables[6][lo]
+            ^ tables[5][data[i + 2] as usize]
+            ^ tables[4][data[i + 3] as usize]
+            ^ tables[3][data[i + 4] as usize]
+            ^ tables[2][data[i + 5] as usize]
+            ^ tables[1][data[i + 6] as usize]
+            ^ tables[0][data[i + 7] as usize];
+        i += SLICES;
+    }
+
+    while i < data.len() {
+        let index = ((crc >> 8) ^ data[i] as u16) & 0xFF;
+        crc = (crc << 8) ^ tables[0][index as usize];
+        i += 1;
     }
     crc
 }
+
+#[derive(Debug, Clone, Copy)]
+pub struct Crc16Ccitt {
+    crc: u16,
+}
+
+impl Default for Crc16Ccitt {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Crc16Ccitt {
+    pub const fn new() -> Self {
+        Self { crc: INIT }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.crc = crc16_ccitt_update(self.crc, data);
+    }
+
+    pub fn finish(&self) -> u16 {
+        self.crc
+    }
+
+    /// Checks that `payload` ends with a tag 63 whose value matches the CRC of everything before it.
+    pub fn verify(payload: &str) -> bool {
+        let Some(split) = payload.len().checked_sub(4) else {
+            return false;
+        };
+        if !payload.is_char_boundary(split) || !payload[..split].ends_with("6304") {
+            return false;
+        }
+
+        let expected = &payload[split..];
+        if !expected.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return false;
+        }
+
+        u16::from_str_radix(expected, 16)
+            .is_ok_and(|expected| crc16_ccitt(&payload[..split]) == expected)
+    }
+}
@@ -0,0 +1,242 @@
+use crate::error::{Error, ErrorKind};
+use crate::models::{
+    AdditionalDataTemplate, CountryCode, Tags, TransactionAmount, TransactionCurrency,
+};
+use crate::tlv::{
+    CrcWriter, SeenTags, TLV, decode_dynamic, split_payload, split_tlv, write_payload_header,
+};
+use crate::write_tlv;
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt::Write;
+
+/// PayNet GUID carried in sub-tag 00 of tag 26.
+pub const DUITNOW_GUID: &str = "A0000006150001";
+/// ISO 4217 numeric code of the Malaysian ringgit.
+pub const MYR_CURRENCY_CODE: &str = "458";
+
+const DUITNOW_TAG: u8 = 26;
+const ACQUIRER_ID_MAX_LENGTH: usize = 11;
+const ACCOUNT_MAX_LENGTH: usize = 32;
+
+/// A Malaysian DuitNow payload. It is dynamic (`12`) when `dynamic` is set or it
+/// carries an amount in ringgit.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DuitNow {
+    /// Participant code of the acquiring institution (sub-tag 26.01).
+    pub acquirer_id: String,
+    /// Merchant account or proxy ID at the acquirer (sub-tag 26.02).
+    pub account: String,
+    pub merchant_category_code: Option<String>,
+    /// Tag 01 is `12` even without an amount, for a single-use QR the payer completes.
+    pub dynamic: bool,
+    /// Must be `TransactionAmount::MYR`.
+    pub amount: Option<TransactionAmount>,
+    pub merchant_name: Option<String>,
+    pub merchant_city: Option<String>,
+    pub additional_data_template: Option<AdditionalDataTemplate>,
+}
+
+impl DuitNow {
+    pub fn is_static(&self) -> bool {
+        !self.dynamic && self.amount.is_none()
+    }
+
+    pub fn decode_qr(qr: &str) -> Result<DuitNow, Error> {
+        let mut template = None;
+        let mut point_of_initiation = None;
+        let mut merchant_category_code = None;
+        let mut currency = None;
+        let mut country = None;
+        let mut amount = None;
+        let mut merchant_name = None;
+        let mut merchant_city = None;
+        let mut additional_data_template = None;
+
+        for (tag, value) in split_payload(qr)? {
+            match tag {
+                1 => point_of_initiation = Some(value),
+                DUITNOW_TAG => template = Some(value),
+                52 => merchant_category_code = Some(value.to_string()),
+                53 => currency = Some(value),
+                54 => {
+                    amount = Some(TransactionAmount::from_string(
+                        &TransactionCurrency::MYR,
+                        value,
+                    )?)
+                }
+                58 => country = Some(value),
+                59 => merchant_name = Some(value.to_string()),
+                60 => merchant_city = Some(value.to_string()),
+                62 => additional_data_template = Some(AdditionalDataTemplate::from_string(value)?),
+                _ => {}
+            }
+        }
+
+        let template = template.ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                "Missing DuitNow account template (tag 26)",
+            )
+        })?;
+        if currency != Some(MYR_CURRENCY_CODE) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "DuitNow currency must be 458 (MYR)",
+            ));
+        }
+        if country != Some(CountryCode::MY.code()) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "DuitNow country code must be MY",
+            ));
+        }
+        let dynamic = decode_dynamic(point_of_initiation, amount.is_some())?;
+
+        let mut guid = None;
+        let mut acquirer_id = None;
+        let mut account = None;
+        let mut seen = SeenTags::default();
+        for (sub_tag, sub_value) in split_tlv(template)? {
+            if !seen.insert(sub_tag) {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Duplicate DuitNow sub-tag 26.{:02}", sub_tag),
+                ));
+            }
+            match sub_tag {
+                0 => guid = Some(sub_value),
+                1 => acquirer_id = Some(sub_value.to_string()),
+                2 => account = Some(sub_value.to_string()),
+                _ => {}
+            }
+        }
+        if guid != Some(DUITNOW_GUID) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("Tag 26 must carry the DuitNow GUID {}", DUITNOW_GUID),
+            ));
+        }
+
+        let duitnow = DuitNow {
+            acquirer_id: acquirer_id.ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    "Missing DuitNow acquirer ID (26.01)",
+                )
+            })?,
+            account: account.ok_or_else(|| {
+                Error::new(ErrorKind::InvalidData, "Missing DuitNow account (26.02)")
+            })?,
+            merchant_category_code,
+            dynamic,
+            amount,
+            merchant_name,
+            merchant_city,
+            additional_data_template,
+        };
+        duitnow.validate()?;
+        Ok(duitnow)
+    }
+
+    pub fn generate_qr(&self) -> Result<String, Error> {
+        let mut qr_code = String::with_capacity(128);
+        self.write_qr(&mut qr_code)?;
+        Ok(qr_code)
+    }
+
+    /// Encodes the payload in canonical order: ascending tags with tag 63 last.
+    pub fn write_qr<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.validate()?;
+
+        let mut qr_code = CrcWriter::new(writer);
+        write_payload_header(&mut qr_code, self.is_static())?;
+
+        let mut template = String::new();
+        write_tlv!(template, (0, DUITNOW_GUID)).map_err(Error::other)?;
+        write_tlv!(template, (1, self.acquirer_id)).map_err(Error::other)?;
+        write_tlv!(template, (2, self.account)).map_err(Error::other)?;
+        Tags::MerchantAccountInformation(DUITNOW_TAG)
+            .validate_length(&template)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+        write_tlv!(&mut qr_code, (DUITNOW_TAG, template)).map_err(Error::other)?;
+
+        if let Some(code) = &self.merchant_category_code {
+            write_tlv!(&mut qr_code, (Tags::MerchantCategoryCode.code(), code))
+                .map_err(Error::other)?;
+        }
+        write_tlv!(
+            &mut qr_code,
+            (Tags::TransactionCurrency.code(), MYR_CURRENCY_CODE)
+        )
+        .map_err(Error::other)?;
+        if let Some(amount) = &self.amount {
+            amount.write_amount(&mut qr_code)?;
+        }
+        write_tlv!(
+            &mut qr_code,
+            (Tags::CountryCode.code(), CountryCode::MY.code())
+        )
+        .map_err(Error::other)?;
+
+        if let Some(name) = &self.merchant_name {
+            Tags::MerchantName
+                .validate_length(name)
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+            write_tlv!(&mut qr_code, (Tags::MerchantName.code(), name)).map_err(Error::other)?;
+        }
+        if let Some(city) = &self.merchant_city {
+            Tags::MerchantCity
+                .validate_length(city)
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+            write_tlv!(&mut qr_code, (Tags::MerchantCity.code(), city)).map_err(Error::other)?;
+        }
+        if let Some(additional_data_template) = &self.additional_data_template {
+            additional_data_template.to_tlv(&mut qr_code)?;
+        }
+
+        qr_code.finish()
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if self.acquirer_id.is_empty()
+            || self.acquirer_id.len() > ACQUIRER_ID_MAX_LENGTH
+            || !self.acquirer_id.bytes().all(|b| b.is_ascii_digit())
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "DuitNow acquirer ID must be 1 to {} digits",
+                    ACQUIRER_ID_MAX_LENGTH
+                ),
+            ));
+        }
+        if self.account.is_empty()
+            || self.account.len() > ACCOUNT_MAX_LENGTH
+            || !self.account.bytes().all(|b| b.is_ascii_alphanumeric())
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "DuitNow account must be 1 to {} letters or digits",
+                    ACCOUNT_MAX_LENGTH
+                ),
+            ));
+        }
+        if let Some(code) = &self.merchant_category_code {
+            Tags::MerchantCategoryCode
+                .validate_length(code)
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+        }
+        if let Some(amount) = &self.amount
+            && amount.currency() != TransactionCurrency::MYR
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("DuitNow amount must be in MYR, got {:?}", amount.currency()),
+            ));
+        }
+        Ok(())
+    }
+}
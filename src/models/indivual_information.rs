@@ -1,7 +1,16 @@
+use crate::error::{Error, ErrorKind};
 use crate::models::{SubTags, Tags};
-use std::io::{Error, ErrorKind};
+use crate::tlv::{SeenTags, split_tlv};
+use alloc::format;
+use alloc::string::{String, ToString};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(
+    feature = "python",
+    pyo3::pyclass(get_all, set_all, eq, from_py_object)
+)]
 pub struct IndivualInformation {
     pub bakong_account_identifier: String,
     pub individual_account_information: Option<String>,
@@ -24,36 +33,22 @@ impl IndivualInformation {
         }
     }
 
-    pub fn from_string(value: &str) -> std::io::Result<Self> {
+    pub fn from_string(value: &str) -> Result<Self, Error> {
         let mut indiv_info = IndivualInformation {
             bakong_account_identifier: String::new(),
             individual_account_information: None,
             acquiring_bank: None,
         };
 
-        let mut slice = value;
-        while !slice.is_empty() {
-            let tag: u8 = slice[0..2].parse().map_err(|_| {
-                Error::new(
+        let mut seen = SeenTags::default();
+        for (tag, data_value) in split_tlv(value)? {
+            if !seen.insert(tag) {
+                return Err(Error::new(
                     ErrorKind::InvalidData,
-                    format!("Tag {} can't be parsed!!!", &slice[0..2]),
-                )
-            })?;
-
-            let length: usize = slice[2..4].parse().map_err(|_| {
-                Error::new(
-                    ErrorKind::InvalidData,
-                    format!("Error length parsing in tag : {}", tag),
-                )
-            })?;
-
-            let value_end = 4 + length;
-            if value_end > slice.len() {
-                return Err(Error::new(ErrorKind::InvalidData, "Length is invalid!!!"));
+                    format!("Duplicate IndivualInformation sub-tag {}", tag),
+                ));
             }
 
-            let data_value: &str = &slice[4..value_end];
-
             let sub_tag =
                 SubTags::from_code(Tags::MerchantAccountInfoIndividual, tag).ok_or_else(|| {
                     Error::new(
@@ -67,8 +62,6 @@ impl IndivualInformation {
                 .map_err(|msg| Error::new(ErrorKind::InvalidData, msg))?;
 
             indiv_info.apply_sub_tag(&sub_tag, data_value);
-
-            slice = &slice[value_end..];
         }
 
         if indiv_info.bakong_account_identifier.is_empty() {
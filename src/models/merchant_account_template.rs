@@ -0,0 +1,63 @@
+use crate::error::{Error, ErrorKind};
+use crate::{TLV, models::Tags};
+use alloc::format;
+use alloc::string::String;
+use core::fmt::Write;
+
+/// Another scheme's merchant account template (tags 26–28 and 31–51), kept verbatim so a decoded
+/// payload regenerates with the same bytes and CRC.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(
+    feature = "python",
+    pyo3::pyclass(get_all, set_all, eq, from_py_object)
+)]
+pub struct MerchantAccountTemplate {
+    pub tag: u8,
+    /// The raw template value, sub-tags included, up to 99 characters.
+    pub value: String,
+}
+
+impl MerchantAccountTemplate {
+    pub fn new(tag: u8, value: String) -> Result<Self, Error> {
+        let template = Self { tag, value };
+        template.validate()?;
+        Ok(template)
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        if !matches!(self.tag, 26..=28 | 31..=51) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Tag {:02} is not a merchant account template (26-28, 31-51)",
+                    self.tag
+                ),
+            ));
+        }
+        if self.value.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Merchant account template {:02} must not be empty",
+                    self.tag
+                ),
+            ));
+        }
+        self.tag()
+            .validate_length(&self.value)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e))
+    }
+}
+
+impl TLV for MerchantAccountTemplate {
+    fn tag(&self) -> Tags {
+        Tags::MerchantAccountInformation(self.tag)
+    }
+
+    fn write_value<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.validate()?;
+        write!(writer, "{}", self.value).map_err(Error::other)
+    }
+}
@@ -1,4 +1,11 @@
-#[derive(Debug)]
+use crate::models::{District, PHNOM_PENH_DISTRICTS};
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "python", pyo3::pyclass(eq, eq_int, from_py_object))]
 pub enum MerchantCity {
     Battambang,
     BanteayMeanChey,
@@ -6,6 +13,7 @@ pub enum MerchantCity {
     KampongChhnang,
     KampongSpeu,
     KampongThom,
+    Kampot,
     Kandal,
     Kep,
     KohKong,
@@ -36,6 +44,7 @@ impl MerchantCity {
             "Kampong Chhnang" => Some(Self::KampongChhnang),
             "Kampong Speu" => Some(Self::KampongSpeu),
             "Kampong Thom" => Some(Self::KampongThom),
+            "Kampot" => Some(Self::Kampot),
             "Kandal" => Some(Self::Kandal),
             "Kep" => Some(Self::Kep),
             "Koh Kong" => Some(Self::KohKong),
@@ -67,6 +76,7 @@ impl MerchantCity {
             Self::KampongChhnang => "Kampong Chhnang",
             Self::KampongSpeu => "Kampong Speu",
             Self::KampongThom => "Kampong Thom",
+            Self::Kampot => "Kampot",
             Self::Kandal => "Kandal",
             Self::Kep => "Kep",
             Self::KohKong => "Koh Kong",
@@ -88,4 +98,87 @@ impl MerchantCity {
             Self::TboungKhmum => "Tboung Khmum",
         }
     }
+
+    /// ISO 3166-2:KH subdivision number, e.g. 12 for Phnom Penh. Poi Pet reports as Banteay
+    /// Meanchey.
+    pub fn province_number(&self) -> u8 {
+        match self {
+            Self::BanteayMeanChey | Self::PoiPet => 1,
+            Self::Battambang => 2,
+            Self::KampongCham => 3,
+            Self::KampongChhnang => 4,
+            Self::KampongSpeu => 5,
+            Self::KampongThom => 6,
+            Self::Kampot => 7,
+            Self::Kandal => 8,
+            Self::KohKong => 9,
+            Self::Kratie => 10,
+            Self::Mondolkiri => 11,
+            Self::PhnomPenh => 12,
+            Self::PreahVihear => 13,
+            Self::PreyVeng => 14,
+            Self::Pursat => 15,
+            Self::Ratanakiri => 16,
+            Self::SiemReap => 17,
+            Self::SihanoukVille => 18,
+            Self::SteungTreng => 19,
+            Self::SvayRieng => 20,
+            Self::Takeo => 21,
+            Self::OddorMeanchey => 22,
+            Self::Kep => 23,
+            Self::Pailin => 24,
+            Self::TboungKhmum => 25,
+        }
+    }
+
+    /// ISO 3166-2:KH code, e.g. `KH-12` for Phnom Penh.
+    pub fn iso_code(&self) -> String {
+        format!("KH-{}", self.province_number())
+    }
+
+    /// Province for an ISO 3166-2:KH code. `KH-1` maps to Banteay Meanchey.
+    pub fn from_iso_code(code: &str) -> Option<Self> {
+        let number: u8 = code.strip_prefix("KH-")?.parse().ok()?;
+        Self::from_province_number(number)
+    }
+
+    pub(crate) fn from_province_number(number: u8) -> Option<Self> {
+        match number {
+            1 => Some(Self::BanteayMeanChey),
+            2 => Some(Self::Battambang),
+            3 => Some(Self::KampongCham),
+            4 => Some(Self::KampongChhnang),
+            5 => Some(Self::KampongSpeu),
+            6 => Some(Self::KampongThom),
+            7 => Some(Self::Kampot),
+            8 => Some(Self::Kandal),
+            9 => Some(Self::KohKong),
+            10 => Some(Self::Kratie),
+            11 => Some(Self::Mondolkiri),
+            12 => Some(Self::PhnomPenh),
+            13 => Some(Self::PreahVihear),
+            14 => Some(Self::PreyVeng),
+            15 => Some(Self::Pursat),
+            16 => Some(Self::Ratanakiri),
+            17 => Some(Self::SiemReap),
+            18 => Some(Self::SihanoukVille),
+            19 => Some(Self::SteungTreng),
+            20 => Some(Self::SvayRieng),
+            21 => Some(Self::Takeo),
+            22 => Some(Self::OddorMeanchey),
+            23 => Some(Self::Kep),
+            24 => Some(Self::Pailin),
+            25 => Some(Self::TboungKhmum),
+            _ => None,
+        }
+    }
+
+    /// Districts of the province embedded in the crate, or `None` when it carries no district
+    /// data for the province. Only Phnom Penh has district data.
+    pub fn districts(&self) -> Option<&'static [District]> {
+        match self {
+            Self::PhnomPenh => Some(PHNOM_PENH_DISTRICTS),
+            _ => None,
+        }
+    }
 }
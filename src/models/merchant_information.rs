@@ -1,7 +1,16 @@
+use crate::error::{Error, ErrorKind};
 use crate::models::{SubTags, Tags};
-use std::io::{Error, ErrorKind};
+use crate::tlv::{SeenTags, split_tlv};
+use alloc::format;
+use alloc::string::{String, ToString};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(
+    feature = "python",
+    pyo3::pyclass(get_all, set_all, eq, from_py_object)
+)]
 pub struct MerchantInformation {
     pub bakong_account_identifier: String,
     pub merchant_identifier: String,
@@ -22,42 +31,26 @@ impl MerchantInformation {
         }
     }
 
-    pub fn from_string(value: &str) -> std::io::Result<Self> {
+    pub fn from_string(value: &str) -> Result<Self, Error> {
         let mut merchant_info = MerchantInformation {
             bakong_account_identifier: String::new(),
             merchant_identifier: String::new(),
             acquiring_bank: String::new(),
         };
 
-        let mut slice = value;
-        while !slice.is_empty() {
-            let tag: u8 = slice[0..2].parse().map_err(|_| {
-                std::io::Error::new(
-                    std::io::ErrorKind::InvalidData,
-                    format!("Tag {} can't be parsed!!!", &slice[0..2]),
-                )
-            })?;
-
-            let length: usize = slice[2..4].parse().map_err(|_| {
-                std::io::Error::new(
-                    std::io::ErrorKind::InvalidData,
-                    format!("Error length parsing in tag : {}", tag),
-                )
-            })?;
-
-            let value_end = 4 + length;
-            if value_end > slice.len() {
-                return Err(std::io::Error::new(
-                    std::io::ErrorKind::InvalidData,
-                    "Length is invalid!!!",
+        let mut seen = SeenTags::default();
+        for (tag, data_value) in split_tlv(value)? {
+            if !seen.insert(tag) {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Duplicate MerchantInformation sub-tag {}", tag),
                 ));
             }
 
-            let data_value: &str = &slice[4..value_end];
             let sub_tag =
                 SubTags::from_code(Tags::MerchantAccountInfoMerchant, tag).ok_or_else(|| {
-                    std::io::Error::new(
-                        std::io::ErrorKind::InvalidData,
+                    Error::new(
+                        ErrorKind::InvalidData,
                         format!("Unknown MerchantInformation sub-tag {}", tag),
                     )
                 })?;
@@ -67,26 +60,25 @@ impl MerchantInformation {
                 .map_err(|msg| Error::new(ErrorKind::InvalidData, msg))?;
 
             merchant_info.apply_sub_tag(&sub_tag, data_value);
-            slice = &slice[value_end..];
         }
 
         if merchant_info.bakong_account_identifier.is_empty() {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
+            return Err(Error::new(
+                ErrorKind::InvalidData,
                 "Bakong account identifier is required but missing",
             ));
         }
 
         if merchant_info.merchant_identifier.is_empty() {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
+            return Err(Error::new(
+                ErrorKind::InvalidData,
                 "Merchant identifier is required but missing",
             ));
         }
 
         if merchant_info.acquiring_bank.is_empty() {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
+            return Err(Error::new(
+                ErrorKind::InvalidData,
                 "Acquiring bank is required but missing",
             ));
         }
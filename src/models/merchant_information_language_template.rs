@@ -1,12 +1,21 @@
+use crate::error::{Error, ErrorKind};
+use crate::tlv::{SeenTags, split_tlv};
 use crate::{
     models::{SubTags, Tags},
     tlv::TLV,
     write_tlv,
 };
-use std::fmt::Write;
-use std::io::{Error, ErrorKind};
-
-#[derive(Debug)]
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt::Write;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(
+    feature = "python",
+    pyo3::pyclass(get_all, set_all, eq, from_py_object)
+)]
 pub struct MerchantInformationLanguageTemplate {
     pub language_preference: String,
     pub merchant_name_alternate_language: String,
@@ -14,7 +23,11 @@ pub struct MerchantInformationLanguageTemplate {
 }
 
 impl TLV for MerchantInformationLanguageTemplate {
-    fn to_tlv(&self, temp: &mut String, buffer: &mut String) -> Result<(), std::io::Error> {
+    fn tag(&self) -> Tags {
+        Tags::MerchantInformationLanguageTemplate
+    }
+
+    fn write_value<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
         SubTags::LanguagePreference
             .validate_length(&self.language_preference)
             .map_err(|msg| Error::new(ErrorKind::InvalidData, msg))?;
@@ -28,67 +41,56 @@ impl TLV for MerchantInformationLanguageTemplate {
             .map_err(|msg| Error::new(ErrorKind::InvalidData, msg))?;
 
         write_tlv!(
-            temp,
+            writer,
             (
                 SubTags::LanguagePreference.code(),
                 &self.language_preference
             )
         )
-        .map_err(|e| Error::new(ErrorKind::Other, e))?;
+        .map_err(Error::other)?;
 
         write_tlv!(
-            temp,
+            writer,
             (
                 SubTags::MerchantNameAlternateLanguage.code(),
                 &self.merchant_name_alternate_language
             )
         )
-        .map_err(|e| Error::new(ErrorKind::Other, e))?;
+        .map_err(Error::other)?;
 
         write_tlv!(
-            temp,
+            writer,
             (
                 SubTags::MerchantCityAlternateLanguage.code(),
                 &self.merchant_city_alternate_language
             )
         )
-        .map_err(|e| Error::new(ErrorKind::Other, e))?;
-
-        write_tlv!(
-            buffer,
-            (Tags::MerchantInformationLanguageTemplate.code(), temp)
-        )
-        .map_err(|e| Error::new(ErrorKind::Other, e))?;
-        temp.clear();
+        .map_err(Error::other)?;
         Ok(())
     }
 }
 
 impl MerchantInformationLanguageTemplate {
-    pub fn from_string(value: &str) -> std::io::Result<Self> {
+    pub fn from_string(value: &str) -> Result<Self, Error> {
         let mut template = MerchantInformationLanguageTemplate {
             language_preference: String::new(),
             merchant_name_alternate_language: String::new(),
             merchant_city_alternate_language: String::new(),
         };
 
-        let mut slice = value;
-
-        while !slice.is_empty() {
-            let tag: u8 = slice[0..2]
-                .parse()
-                .map_err(|_| Error::new(ErrorKind::InvalidData, "Invalid sub-tag"))?;
-
-            let length: usize = slice[2..4]
-                .parse()
-                .map_err(|_| Error::new(ErrorKind::InvalidData, "Invalid length"))?;
-
-            let end = 4 + length;
-            if end > slice.len() {
-                return Err(Error::new(ErrorKind::InvalidData, "Length error"));
+        let mut seen = SeenTags::default();
+
+        for (tag, data) in split_tlv(value)? {
+            if !seen.insert(tag) {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!(
+                        "Duplicate MerchantInformationLanguageTemplate sub-tag {}",
+                        tag
+                    ),
+                ));
             }
 
-            let data = &slice[4..end];
             let sub_tag = SubTags::from_code(Tags::MerchantInformationLanguageTemplate, tag)
                 .ok_or_else(|| {
                     Error::new(
@@ -114,8 +116,6 @@ impl MerchantInformationLanguageTemplate {
                 }
                 _ => {}
             }
-
-            slice = &slice[end..];
         }
 
         if template.language_preference.is_empty()
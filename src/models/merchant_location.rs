@@ -0,0 +1,207 @@
+use crate::error::{Error, ErrorKind};
+use crate::models::{CountryCode, MerchantCity, Tags};
+use alloc::format;
+use alloc::string::{String, ToString};
+
+/// A district (khan, srok or krong) and its 4-digit geocode, whose first two digits are the
+/// province number of its ISO 3166-2:KH code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct District {
+    pub code: &'static str,
+    pub name: &'static str,
+}
+
+/// The districts of Phnom Penh, the only province whose districts the crate embeds.
+pub(crate) const PHNOM_PENH_DISTRICTS: &[District] = &[
+    District {
+        code: "1201",
+        name: "Chamkar Mon",
+    },
+    District {
+        code: "1202",
+        name: "Doun Penh",
+    },
+    District {
+        code: "1203",
+        name: "Prampir Meakkakra",
+    },
+    District {
+        code: "1204",
+        name: "Tuol Kouk",
+    },
+    District {
+        code: "1205",
+        name: "Dangkao",
+    },
+    District {
+        code: "1206",
+        name: "Mean Chey",
+    },
+    District {
+        code: "1207",
+        name: "Russey Keo",
+    },
+    District {
+        code: "1208",
+        name: "Saensokh",
+    },
+    District {
+        code: "1209",
+        name: "Pur SenChey",
+    },
+    District {
+        code: "1210",
+        name: "Chraoy Chongvar",
+    },
+    District {
+        code: "1211",
+        name: "Praek Pnov",
+    },
+    District {
+        code: "1212",
+        name: "Chbar Ampov",
+    },
+    District {
+        code: "1213",
+        name: "Boeng Keng Kang",
+    },
+    District {
+        code: "1214",
+        name: "Kamboul",
+    },
+];
+
+/// Where a merchant trades, down to the commune, for regulator reporting. Only the province is
+/// carried in the QR (tag 60); the postal code (tag 61) also encodes the district and commune.
+///
+/// The crate embeds all 25 provinces, the districts of Phnom Penh and no commune data. District and
+/// commune names elsewhere come from the caller and are only checked for being non-empty.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(
+    feature = "python",
+    pyo3::pyclass(get_all, set_all, eq, from_py_object)
+)]
+pub struct MerchantLocation {
+    pub province: MerchantCity,
+    /// Checked against `MerchantCity::districts` when the crate has the province's districts.
+    pub district: Option<String>,
+    /// Free text; requires a district.
+    pub commune: Option<String>,
+}
+
+impl MerchantLocation {
+    pub fn new(
+        province: MerchantCity,
+        district: Option<String>,
+        commune: Option<String>,
+    ) -> Result<Self, Error> {
+        let location = Self {
+            province,
+            district,
+            commune,
+        };
+        location.validate()?;
+        Ok(location)
+    }
+
+    /// Province and, when the crate embeds it, district of a 6-digit Cambodian postal code.
+    pub fn from_postal_code(postal_code: &str) -> Result<Self, Error> {
+        validate_postal_code(postal_code, &CountryCode::KH)?;
+        let province = postal_code[..2]
+            .parse()
+            .ok()
+            .and_then(MerchantCity::from_province_number)
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!(
+                        "Postal code '{}' is in a province without a MerchantCity",
+                        postal_code
+                    ),
+                )
+            })?;
+        let district = province
+            .districts()
+            .unwrap_or_default()
+            .iter()
+            .find(|district| district.code == &postal_code[..4])
+            .map(|district| district.name.to_string());
+
+        Ok(Self {
+            province,
+            district,
+            commune: None,
+        })
+    }
+
+    pub fn iso_code(&self) -> String {
+        self.province.iso_code()
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        if let Some(district) = &self.district {
+            let known = self.province.districts();
+            if district.is_empty()
+                || known.is_some_and(|known| !known.iter().any(|known| known.name == district))
+            {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!(
+                        "Unknown district '{}' in {}",
+                        district,
+                        self.province.city()
+                    ),
+                ));
+            }
+        }
+        match &self.commune {
+            Some(_) if self.district.is_none() => Err(Error::new(
+                ErrorKind::InvalidData,
+                "Commune requires a district",
+            )),
+            Some(commune) if commune.is_empty() => Err(Error::new(
+                ErrorKind::InvalidData,
+                "Commune must not be empty",
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Checks a tag 61 value: up to 10 letters or digits, and for Cambodia the 6-digit code whose
+/// first two digits are the province number.
+pub(crate) fn validate_postal_code(postal_code: &str, country: &CountryCode) -> Result<(), Error> {
+    Tags::PostalCode
+        .validate_length(postal_code)
+        .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+    if postal_code.is_empty() || !postal_code.bytes().all(|b| b.is_ascii_alphanumeric()) {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "Postal code must be letters or digits",
+        ));
+    }
+    if *country == CountryCode::KH {
+        if postal_code.len() != 6 || !postal_code.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Cambodian postal code must be exactly 6 digits",
+            ));
+        }
+        if !matches!(postal_code[..2].parse::<u8>(), Ok(1..=25)) {
+            return Err(unknown_province(postal_code));
+        }
+    }
+    Ok(())
+}
+
+fn unknown_province(postal_code: &str) -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        format!(
+            "Postal code '{}' does not start with a province number",
+            postal_code
+        ),
+    )
+}
@@ -1,19 +1,30 @@
+use crate::error::{Error, ErrorKind};
 use crate::{
     models::{IndivualInformation, MerchantInformation, SubTags, Tags},
     tlv::TLV,
+    write_tlv,
 };
-use std::fmt::Write;
-use std::io::{Error, ErrorKind};
+use core::fmt::Write;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "python", pyo3::pyclass(eq, from_py_object))]
 pub enum MerchantType {
     Indivual(IndivualInformation),
     Merchant(MerchantInformation),
 }
 
 impl TLV for MerchantType {
-    fn to_tlv(&self, temp: &mut String, buffer: &mut String) -> Result<(), std::io::Error> {
-        let tag = match &self {
+    fn tag(&self) -> Tags {
+        match self {
+            MerchantType::Indivual(_) => Tags::MerchantAccountInfoIndividual,
+            MerchantType::Merchant(_) => Tags::MerchantAccountInfoMerchant,
+        }
+    }
+
+    fn write_value<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        match &self {
             MerchantType::Indivual(indivual_information) => {
                 SubTags::BakongAccountIdentifier
                     .validate_length(&indivual_information.bakong_account_identifier)
@@ -28,44 +39,30 @@ impl TLV for MerchantType {
                         "Bakong account identifier must contain exactly one '@'",
                     ));
                 }
-                write!(
-                    temp,
-                    "{:02}{:02}{}",
-                    SubTags::BakongAccountIdentifier.code(),
-                    indivual_information.bakong_account_identifier.len(),
-                    indivual_information.bakong_account_identifier
+                write_tlv!(
+                    writer,
+                    (
+                        SubTags::BakongAccountIdentifier.code(),
+                        indivual_information.bakong_account_identifier
+                    )
                 )
-                .map_err(|e| Error::new(ErrorKind::Other, e))?;
+                .map_err(Error::other)?;
 
                 if let Some(info) = &indivual_information.individual_account_information {
                     SubTags::IndividualAccountInformation
                         .validate_length(info)
                         .map_err(|msg| Error::new(ErrorKind::InvalidData, msg))?;
-                    write!(
-                        temp,
-                        "{:02}{:02}{}",
-                        SubTags::IndividualAccountInformation.code(),
-                        info.len(),
-                        info
-                    )
-                    .map_err(|e| Error::new(ErrorKind::Other, e))?;
+                    write_tlv!(writer, (SubTags::IndividualAccountInformation.code(), info))
+                        .map_err(Error::other)?;
                 }
 
                 if let Some(bank) = &indivual_information.acquiring_bank {
                     SubTags::AcquiringBank
                         .validate_length(bank)
                         .map_err(|msg| Error::new(ErrorKind::InvalidData, msg))?;
-                    write!(
-                        temp,
-                        "{:02}{:02}{}",
-                        SubTags::AcquiringBank.code(),
-                        bank.len(),
-                        bank
-                    )
-                    .map_err(|e| Error::new(ErrorKind::Other, e))?;
+                    write_tlv!(writer, (SubTags::AcquiringBank.code(), bank))
+                        .map_err(Error::other)?;
                 }
-
-                Tags::MerchantAccountInfoIndividual
             }
             MerchantType::Merchant(merchant_information) => {
                 SubTags::BakongAccountIdentifier
@@ -79,46 +76,41 @@ impl TLV for MerchantType {
                         "Bakong account identifier must contain exactly one '@'",
                     ));
                 }
-                write!(
-                    temp,
-                    "{:02}{:02}{}",
-                    SubTags::BakongAccountIdentifier.code(),
-                    merchant_information.bakong_account_identifier.len(),
-                    merchant_information.bakong_account_identifier
+                write_tlv!(
+                    writer,
+                    (
+                        SubTags::BakongAccountIdentifier.code(),
+                        merchant_information.bakong_account_identifier
+                    )
                 )
-                .map_err(|e| Error::new(ErrorKind::Other, e))?;
+                .map_err(Error::other)?;
 
                 SubTags::MerchantIdentifier
                     .validate_length(&merchant_information.merchant_identifier)
                     .map_err(|msg| Error::new(ErrorKind::InvalidData, msg))?;
-                write!(
-                    temp,
-                    "{:02}{:02}{}",
-                    SubTags::MerchantIdentifier.code(),
-                    merchant_information.merchant_identifier.len(),
-                    merchant_information.merchant_identifier
+                write_tlv!(
+                    writer,
+                    (
+                        SubTags::MerchantIdentifier.code(),
+                        merchant_information.merchant_identifier
+                    )
                 )
-                .map_err(|e| Error::new(ErrorKind::Other, e))?;
+                .map_err(Error::other)?;
 
                 SubTags::AcquiringBank
                     .validate_length(&merchant_information.acquiring_bank)
                     .map_err(|msg| Error::new(ErrorKind::InvalidData, msg))?;
-                write!(
-                    temp,
-                    "{:02}{:02}{}",
-                    SubTags::AcquiringBank.code(),
-                    merchant_information.acquiring_bank.len(),
-                    merchant_information.acquiring_bank
+                write_tlv!(
+                    writer,
+                    (
+                        SubTags::AcquiringBank.code(),
+                        merchant_information.acquiring_bank
+                    )
                 )
-                .map_err(|e| Error::new(ErrorKind::Other, e))?;
-
-                Tags::MerchantAccountInfoMerchant
+                .map_err(Error::other)?;
             }
-        };
+        }
 
-        write!(buffer, "{:02}{:02}{}", tag.code(), temp.len(), temp)
-            .map_err(|e| Error::new(ErrorKind::Other, e))?;
-        temp.clear();
         Ok(())
     }
 }
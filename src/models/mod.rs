@@ -3,26 +3,51 @@ mod additional_data_template;
 mod bakong;
 mod country_code;
 mod crc;
+mod duitnow;
 mod indivual_information;
+mod merchant_account_template;
 mod merchant_city;
 mod merchant_information;
 mod merchant_information_language_template;
+mod merchant_location;
 mod merchant_type;
+mod network_merchant_account;
 mod point_of_initial_method;
+mod promptpay;
+mod qris;
 mod sub_tag;
 mod tag;
+mod tip_or_convenience_fee;
 mod transaction_currency;
+mod unionpay_merchant;
+mod unreserved_template;
+mod vietqr;
 pub use additional_data_field::AdditionalDataField;
 pub use additional_data_template::AdditionalDataTemplate;
 pub use bakong::Bakong;
 pub use country_code::CountryCode;
-pub use crc::crc16_ccitt;
+pub use crc::{Crc16Ccitt, crc16_ccitt, crc16_ccitt_update};
+pub use duitnow::{DUITNOW_GUID, DuitNow, MYR_CURRENCY_CODE};
 pub use indivual_information::IndivualInformation;
+pub use merchant_account_template::MerchantAccountTemplate;
 pub use merchant_city::MerchantCity;
 pub use merchant_information::MerchantInformation;
 pub use merchant_information_language_template::MerchantInformationLanguageTemplate;
+pub use merchant_location::{District, MerchantLocation};
+pub(crate) use merchant_location::{PHNOM_PENH_DISTRICTS, validate_postal_code};
 pub use merchant_type::MerchantType;
+pub use network_merchant_account::{CardNetwork, NetworkMerchantAccount};
 pub use point_of_initial_method::PointOfInitialMethod;
+pub use promptpay::{
+    PROMPTPAY_BILL_PAYMENT_AID, PROMPTPAY_CREDIT_TRANSFER_AID, PromptPay, PromptPayAccount,
+    THB_CURRENCY_CODE,
+};
+pub use qris::{IDR_CURRENCY_CODE, QRIS_GUID, Qris, QrisAccount, QrisMerchantCriteria};
 pub use sub_tag::SubTags;
 pub use tag::Tags;
+pub use tip_or_convenience_fee::{TipOrConvenienceFee, TipOrConvenienceIndicator};
 pub use transaction_currency::{TransactionAmount, TransactionCurrency};
+pub use unionpay_merchant::UnionPayMerchant;
+pub(crate) use unreserved_template::MergedTemplate;
+pub use unreserved_template::{GuidRegistry, UnreservedField, UnreservedTemplate};
+pub use vietqr::{VIETQR_GUID, VND_CURRENCY_CODE, VietQr, VietQrService};
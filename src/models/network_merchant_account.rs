@@ -0,0 +1,104 @@
+use crate::error::{Error, ErrorKind};
+use crate::{TLV, models::Tags};
+use alloc::format;
+use alloc::string::String;
+use core::fmt::Write;
+
+/// The payment network EMVCo assigns a merchant account tag to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "python", pyo3::pyclass(eq, eq_int, from_py_object))]
+pub enum CardNetwork {
+    /// Tags 02–03.
+    Visa,
+    /// Tags 04–05.
+    Mastercard,
+    /// Tags 06–08 and 17–25, reserved by EMVCo.
+    Emvco,
+    /// Tags 09–10.
+    Discover,
+    /// Tags 11–12.
+    Amex,
+    /// Tags 13–14.
+    Jcb,
+    /// Tags 15–16; tag 15 is decoded as a `UnionPayMerchant`.
+    UnionPay,
+}
+
+impl CardNetwork {
+    pub fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            2..=3 => Some(Self::Visa),
+            4..=5 => Some(Self::Mastercard),
+            6..=8 | 17..=25 => Some(Self::Emvco),
+            9..=10 => Some(Self::Discover),
+            11..=12 => Some(Self::Amex),
+            13..=14 => Some(Self::Jcb),
+            15..=16 => Some(Self::UnionPay),
+            _ => None,
+        }
+    }
+}
+
+/// A card network merchant account (tags 02–14 and 16–25) carried next to the Bakong account.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(
+    feature = "python",
+    pyo3::pyclass(get_all, set_all, eq, from_py_object)
+)]
+pub struct NetworkMerchantAccount {
+    pub tag: u8,
+    /// The merchant identifier as issued by the network, up to 99 characters.
+    pub merchant_account: String,
+}
+
+impl NetworkMerchantAccount {
+    pub fn new(tag: u8, merchant_account: String) -> Result<Self, Error> {
+        let account = Self {
+            tag,
+            merchant_account,
+        };
+        account.validate()?;
+        Ok(account)
+    }
+
+    pub fn network(&self) -> Option<CardNetwork> {
+        CardNetwork::from_tag(self.tag)
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        // Tag 15 belongs to `Bakong::unionpay_merchant`.
+        if self.tag == 15 || self.network().is_none() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Tag {:02} is not a card network merchant account (02-14, 16-25)",
+                    self.tag
+                ),
+            ));
+        }
+        if self.merchant_account.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("Merchant account in tag {:02} must not be empty", self.tag),
+            ));
+        }
+        self.tag()
+            .validate_length(&self.merchant_account)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e))
+    }
+}
+
+impl TLV for NetworkMerchantAccount {
+    fn tag(&self) -> Tags {
+        Tags::NetworkMerchantAccount(self.tag)
+    }
+
+    fn write_value<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.validate()?;
+        write!(writer, "{}", self.merchant_account).map_err(Error::other)
+    }
+}
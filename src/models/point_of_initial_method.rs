@@ -1,7 +1,11 @@
 use super::{AdditionalDataField, TransactionAmount, TransactionCurrency};
-use std::io::Error;
+use crate::error::Error;
+use core::fmt::Write;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "python", pyo3::pyclass(eq, from_py_object))]
 pub enum PointOfInitialMethod {
     Static(TransactionCurrency),
     Dynamic {
@@ -11,7 +15,7 @@ pub enum PointOfInitialMethod {
 }
 
 impl PointOfInitialMethod {
-    pub fn write_currency(&self, buffer: &mut String) -> Result<(), Error> {
+    pub fn write_currency<W: Write + ?Sized>(&self, buffer: &mut W) -> Result<(), Error> {
         match self {
             PointOfInitialMethod::Static(transaction_currency) => {
                 transaction_currency.write_currency(buffer)
@@ -23,6 +27,7 @@ impl PointOfInitialMethod {
         }
     }
 
+    #[cfg(feature = "std")]
     pub fn from_string(
         is_static: bool,
         currency: &TransactionCurrency,
@@ -34,8 +39,8 @@ impl PointOfInitialMethod {
                 Ok(PointOfInitialMethod::Static(currency))
             }
             false => {
-                let amount = TransactionAmount::from_string(&currency, value)?;
-                let additional_data_field = if value.len() > 0 {
+                let amount = TransactionAmount::from_string(currency, value)?;
+                let additional_data_field = if !value.is_empty() {
                     Some(AdditionalDataField::from_string(value)?)
                 } else {
                     None
@@ -49,6 +54,13 @@ impl PointOfInitialMethod {
         }
     }
 
+    pub fn currency(&self) -> TransactionCurrency {
+        match self {
+            Self::Static(currency) => currency.clone(),
+            Self::Dynamic { amount, .. } => amount.currency(),
+        }
+    }
+
     pub fn value(&self) -> &'static str {
         match self {
             Self::Static(_) => "11",
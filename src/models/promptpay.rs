@@ -0,0 +1,334 @@
+use crate::error::{Error, ErrorKind};
+use crate::models::{CountryCode, Tags, TransactionAmount, TransactionCurrency};
+use crate::tlv::{
+    CrcWriter, SeenTags, TLV, decode_dynamic, split_payload, split_tlv, write_payload_header,
+};
+use crate::write_tlv;
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt::Write;
+
+/// Application identifier of PromptPay credit transfers (tag 29).
+pub const PROMPTPAY_CREDIT_TRANSFER_AID: &str = "A000000677010111";
+/// Application identifier of PromptPay bill payments (tag 30).
+pub const PROMPTPAY_BILL_PAYMENT_AID: &str = "A000000677010112";
+/// ISO 4217 numeric code of the Thai baht.
+pub const THB_CURRENCY_CODE: &str = "764";
+
+const REFERENCE_MAX_LENGTH: usize = 20;
+
+/// Who a PromptPay payment goes to.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PromptPayAccount {
+    /// `0066` followed by the 9-digit national mobile number.
+    Mobile(String),
+    /// 13-digit national ID or tax ID.
+    NationalId(String),
+    /// 15-digit e-wallet ID.
+    EWallet(String),
+    BillPayment {
+        /// 13-digit tax ID followed by a 2-digit suffix.
+        biller_id: String,
+        reference_1: String,
+        reference_2: Option<String>,
+    },
+}
+
+impl PromptPayAccount {
+    /// Builds a mobile account from a Thai number such as `081-234-5678` or `+66812345678`.
+    pub fn mobile(number: &str) -> Result<Self, Error> {
+        let digits: String = number.chars().filter(char::is_ascii_digit).collect();
+        let national = digits
+            .strip_prefix("66")
+            .or_else(|| digits.strip_prefix('0'))
+            .unwrap_or(&digits);
+        if national.len() != 9 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Invalid Thai mobile number '{}'", number),
+            ));
+        }
+        Ok(Self::Mobile(format!("0066{}", national)))
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        fn digits(name: &str, value: &str, length: usize) -> Result<(), Error> {
+            if value.len() != length || !value.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("PromptPay {} must be exactly {} digits", name, length),
+                ));
+            }
+            Ok(())
+        }
+
+        fn reference(name: &str, value: &str) -> Result<(), Error> {
+            if value.is_empty() || value.len() > REFERENCE_MAX_LENGTH {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!(
+                        "PromptPay {} must be 1 to {} characters",
+                        name, REFERENCE_MAX_LENGTH
+                    ),
+                ));
+            }
+            Ok(())
+        }
+
+        match self {
+            Self::Mobile(number) => {
+                digits("mobile number", number, 13)?;
+                if !number.starts_with("0066") {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        "PromptPay mobile number must start with 0066",
+                    ));
+                }
+                Ok(())
+            }
+            Self::NationalId(id) => digits("national ID", id, 13),
+            Self::EWallet(id) => digits("e-wallet ID", id, 15),
+            Self::BillPayment {
+                biller_id,
+                reference_1,
+                reference_2,
+            } => {
+                digits("biller ID", biller_id, 15)?;
+                reference("reference 1", reference_1)?;
+                if let Some(reference_2) = reference_2 {
+                    reference("reference 2", reference_2)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn from_template(tag: u8, value: &str) -> Result<Self, Error> {
+        let mut aid = None;
+        let mut fields: [Option<&str>; 4] = [None; 4];
+        let mut seen = SeenTags::default();
+
+        for (sub_tag, sub_value) in split_tlv(value)? {
+            if !seen.insert(sub_tag) {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Duplicate PromptPay sub-tag {:02}.{:02}", tag, sub_tag),
+                ));
+            }
+            match sub_tag {
+                0 => aid = Some(sub_value),
+                1..=3 => fields[usize::from(sub_tag)] = Some(sub_value),
+                _ => {}
+            }
+        }
+
+        let account = match (tag, aid) {
+            (29, Some(PROMPTPAY_CREDIT_TRANSFER_AID)) => match fields {
+                [_, Some(mobile), None, None] => Self::Mobile(mobile.to_string()),
+                [_, None, Some(id), None] => Self::NationalId(id.to_string()),
+                [_, None, None, Some(wallet)] => Self::EWallet(wallet.to_string()),
+                _ => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        "PromptPay credit transfer must carry exactly one of sub-tags 01, 02 and 03",
+                    ));
+                }
+            },
+            (30, Some(PROMPTPAY_BILL_PAYMENT_AID)) => match fields {
+                [_, Some(biller_id), Some(reference_1), reference_2] => Self::BillPayment {
+                    biller_id: biller_id.to_string(),
+                    reference_1: reference_1.to_string(),
+                    reference_2: reference_2.map(str::to_string),
+                },
+                _ => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        "PromptPay bill payment requires a biller ID and reference 1",
+                    ));
+                }
+            },
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Tag {:02} does not carry a PromptPay application ID", tag),
+                ));
+            }
+        };
+
+        account.validate()?;
+        Ok(account)
+    }
+}
+
+impl TLV for PromptPayAccount {
+    fn tag(&self) -> Tags {
+        match self {
+            Self::BillPayment { .. } => Tags::MerchantAccountInfoMerchant,
+            _ => Tags::MerchantAccountInfoIndividual,
+        }
+    }
+
+    fn write_value<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.validate()?;
+        match self {
+            Self::Mobile(number) => {
+                write_tlv!(writer, (0, PROMPTPAY_CREDIT_TRANSFER_AID)).map_err(Error::other)?;
+                write_tlv!(writer, (1, number)).map_err(Error::other)
+            }
+            Self::NationalId(id) => {
+                write_tlv!(writer, (0, PROMPTPAY_CREDIT_TRANSFER_AID)).map_err(Error::other)?;
+                write_tlv!(writer, (2, id)).map_err(Error::other)
+            }
+            Self::EWallet(id) => {
+                write_tlv!(writer, (0, PROMPTPAY_CREDIT_TRANSFER_AID)).map_err(Error::other)?;
+                write_tlv!(writer, (3, id)).map_err(Error::other)
+            }
+            Self::BillPayment {
+                biller_id,
+                reference_1,
+                reference_2,
+            } => {
+                write_tlv!(writer, (0, PROMPTPAY_BILL_PAYMENT_AID)).map_err(Error::other)?;
+                write_tlv!(writer, (1, biller_id)).map_err(Error::other)?;
+                write_tlv!(writer, (2, reference_1)).map_err(Error::other)?;
+                if let Some(reference_2) = reference_2 {
+                    write_tlv!(writer, (3, reference_2)).map_err(Error::other)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A Thai PromptPay payload. It is dynamic (`12`) when `dynamic` is set or it carries an amount.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PromptPay {
+    pub account: PromptPayAccount,
+    /// Tag 01 is `12` even without an amount, for a single-use QR the payer completes.
+    pub dynamic: bool,
+    /// Must be `TransactionAmount::THB`.
+    pub amount: Option<TransactionAmount>,
+    pub merchant_name: Option<String>,
+    pub merchant_city: Option<String>,
+}
+
+impl PromptPay {
+    pub fn is_static(&self) -> bool {
+        !self.dynamic && self.amount.is_none()
+    }
+
+    pub fn decode_qr(qr: &str) -> Result<PromptPay, Error> {
+        let mut account = None;
+        let mut point_of_initiation = None;
+        let mut currency = None;
+        let mut country = None;
+        let mut amount = None;
+        let mut merchant_name = None;
+        let mut merchant_city = None;
+
+        for (tag, value) in split_payload(qr)? {
+            match tag {
+                1 => point_of_initiation = Some(value),
+                29 | 30 => {
+                    if account.is_some() {
+                        return Err(Error::new(
+                            ErrorKind::InvalidData,
+                            "Multiple PromptPay account templates found (29 + 30)",
+                        ));
+                    }
+                    account = Some(PromptPayAccount::from_template(tag, value)?);
+                }
+                53 => currency = Some(value),
+                54 => {
+                    amount = Some(TransactionAmount::from_string(
+                        &TransactionCurrency::THB,
+                        value,
+                    )?)
+                }
+                58 => country = Some(value),
+                59 => merchant_name = Some(value.to_string()),
+                60 => merchant_city = Some(value.to_string()),
+                _ => {}
+            }
+        }
+
+        let account = account.ok_or_else(|| {
+            Error::new(ErrorKind::InvalidData, "Missing PromptPay account template")
+        })?;
+        if currency != Some(THB_CURRENCY_CODE) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "PromptPay currency must be 764 (THB)",
+            ));
+        }
+        if country != Some(CountryCode::TH.code()) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "PromptPay country code must be TH",
+            ));
+        }
+        let dynamic = decode_dynamic(point_of_initiation, amount.is_some())?;
+
+        Ok(PromptPay {
+            account,
+            dynamic,
+            amount,
+            merchant_name,
+            merchant_city,
+        })
+    }
+
+    pub fn generate_qr(&self) -> Result<String, Error> {
+        let mut qr_code = String::with_capacity(128);
+        self.write_qr(&mut qr_code)?;
+        Ok(qr_code)
+    }
+
+    /// Encodes the payload in canonical order: ascending tags with tag 63 last.
+    pub fn write_qr<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        let mut qr_code = CrcWriter::new(writer);
+        write_payload_header(&mut qr_code, self.is_static())?;
+        self.account.to_tlv(&mut qr_code)?;
+        write_tlv!(
+            &mut qr_code,
+            (Tags::TransactionCurrency.code(), THB_CURRENCY_CODE)
+        )
+        .map_err(Error::other)?;
+
+        if let Some(amount) = &self.amount {
+            if amount.currency() != TransactionCurrency::THB {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!(
+                        "PromptPay amount must be in THB, got {:?}",
+                        amount.currency()
+                    ),
+                ));
+            }
+            amount.write_amount(&mut qr_code)?;
+        }
+
+        write_tlv!(
+            &mut qr_code,
+            (Tags::CountryCode.code(), CountryCode::TH.code())
+        )
+        .map_err(Error::other)?;
+
+        if let Some(name) = &self.merchant_name {
+            Tags::MerchantName
+                .validate_length(name)
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+            write_tlv!(&mut qr_code, (Tags::MerchantName.code(), name)).map_err(Error::other)?;
+        }
+        if let Some(city) = &self.merchant_city {
+            Tags::MerchantCity
+                .validate_length(city)
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+            write_tlv!(&mut qr_code, (Tags::MerchantCity.code(), city)).map_err(Error::other)?;
+        }
+
+        qr_code.finish()
+    }
+}
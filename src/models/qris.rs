@@ -0,0 +1,473 @@
+use crate::error::{Error, ErrorKind};
+use crate::models::{AdditionalDataTemplate, CountryCode, Tags};
+use crate::tlv::{
+    CrcWriter, SeenTags, TLV, decode_dynamic, split_payload, split_tlv, write_payload_header,
+};
+use crate::write_tlv;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+/// Reverse-domain GUID of the national QRIS repository, carried in sub-tag 00 of tag 51.
+pub const QRIS_GUID: &str = "ID.CO.QRIS.WWW";
+/// ISO 4217 numeric code of the Indonesian rupiah.
+pub const IDR_CURRENCY_CODE: &str = "360";
+
+const NMID_TAG: u8 = 51;
+const GUID_MAX_LENGTH: usize = 32;
+const MERCHANT_PAN_MAX_LENGTH: usize = 19;
+const MERCHANT_ID_MAX_LENGTH: usize = 15;
+
+/// Bank Indonesia merchant size classification (sub-tag 03).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum QrisMerchantCriteria {
+    /// `UMI`
+    Micro,
+    /// `UKE`
+    Small,
+    /// `UME`
+    Medium,
+    /// `UBE`
+    Large,
+    /// `URE`
+    Regular,
+}
+
+impl QrisMerchantCriteria {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Micro => "UMI",
+            Self::Small => "UKE",
+            Self::Medium => "UME",
+            Self::Large => "UBE",
+            Self::Regular => "URE",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "UMI" => Some(Self::Micro),
+            "UKE" => Some(Self::Small),
+            "UME" => Some(Self::Medium),
+            "UBE" => Some(Self::Large),
+            "URE" => Some(Self::Regular),
+            _ => None,
+        }
+    }
+
+    fn parse(code: &str, path: &str) -> Result<Self, Error> {
+        Self::from_code(code).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Unknown QRIS merchant criteria '{}' at {}", code, path),
+            )
+        })
+    }
+}
+
+/// One of the issuer templates 26–45 of a QRIS payload.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QrisAccount {
+    pub tag: u8,
+    /// Reverse-domain GUID of the issuer, e.g. `ID.CO.BANKMANDIRI.WWW`.
+    pub guid: String,
+    /// Merchant PAN (sub-tag 01).
+    pub merchant_pan: Option<String>,
+    /// Merchant ID at the issuer (sub-tag 02).
+    pub merchant_id: Option<String>,
+    pub criteria: Option<QrisMerchantCriteria>,
+}
+
+impl QrisAccount {
+    fn from_template(tag: u8, value: &str) -> Result<Self, Error> {
+        let mut guid = None;
+        let mut merchant_pan = None;
+        let mut merchant_id = None;
+        let mut criteria = None;
+        let mut seen = SeenTags::default();
+
+        for (sub_tag, sub_value) in split_tlv(value)? {
+            if !seen.insert(sub_tag) {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Duplicate QRIS sub-tag {:02}.{:02}", tag, sub_tag),
+                ));
+            }
+            match sub_tag {
+                0 => guid = Some(sub_value.to_string()),
+                1 => merchant_pan = Some(sub_value.to_string()),
+                2 => merchant_id = Some(sub_value.to_string()),
+                3 => {
+                    criteria = Some(QrisMerchantCriteria::parse(
+                        sub_value,
+                        &format!("{:02}.03", tag),
+                    )?)
+                }
+                _ => {}
+            }
+        }
+
+        Ok(QrisAccount {
+            tag,
+            guid: guid.ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Missing QRIS GUID ({:02}.00)", tag),
+                )
+            })?,
+            merchant_pan,
+            merchant_id,
+            criteria,
+        })
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if !(26..=45).contains(&self.tag) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("QRIS account tag must be 26 to 45, got {}", self.tag),
+            ));
+        }
+        if self.guid.is_empty() || self.guid.len() > GUID_MAX_LENGTH {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "QRIS GUID at {:02}.00 must be 1 to {} characters",
+                    self.tag, GUID_MAX_LENGTH
+                ),
+            ));
+        }
+        if let Some(pan) = &self.merchant_pan
+            && (pan.is_empty()
+                || pan.len() > MERCHANT_PAN_MAX_LENGTH
+                || !pan.bytes().all(|b| b.is_ascii_digit()))
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "QRIS merchant PAN at {:02}.01 must be 1 to {} digits",
+                    self.tag, MERCHANT_PAN_MAX_LENGTH
+                ),
+            ));
+        }
+        if let Some(id) = &self.merchant_id
+            && (id.is_empty() || id.len() > MERCHANT_ID_MAX_LENGTH)
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "QRIS merchant ID at {:02}.02 must be 1 to {} characters",
+                    self.tag, MERCHANT_ID_MAX_LENGTH
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl TLV for QrisAccount {
+    fn tag(&self) -> Tags {
+        Tags::MerchantAccountInformation(self.tag)
+    }
+
+    fn write_value<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        write_tlv!(writer, (0, self.guid)).map_err(Error::other)?;
+        if let Some(pan) = &self.merchant_pan {
+            write_tlv!(writer, (1, pan)).map_err(Error::other)?;
+        }
+        if let Some(id) = &self.merchant_id {
+            write_tlv!(writer, (2, id)).map_err(Error::other)?;
+        }
+        if let Some(criteria) = &self.criteria {
+            write_tlv!(writer, (3, criteria.code())).map_err(Error::other)?;
+        }
+        Ok(())
+    }
+}
+
+/// An Indonesian QRIS payload. It is dynamic (`12`) when `dynamic` is set or it carries an
+/// amount in rupiah.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Qris {
+    /// Issuer templates, at least one; written in ascending tag order.
+    pub accounts: Vec<QrisAccount>,
+    /// National Merchant ID from the QRIS repository (sub-tag 51.02).
+    pub nmid: String,
+    /// Merchant criteria from the QRIS repository (sub-tag 51.03).
+    pub criteria: QrisMerchantCriteria,
+    pub merchant_category_code: String,
+    /// Tag 01 is `12` even without an amount, for a single-use QR the payer completes.
+    pub dynamic: bool,
+    pub amount: Option<u64>,
+    pub merchant_name: String,
+    pub merchant_city: String,
+    /// 5-digit postal code (tag 61).
+    pub postal_code: String,
+    pub additional_data_template: Option<AdditionalDataTemplate>,
+}
+
+impl Qris {
+    pub fn is_static(&self) -> bool {
+        !self.dynamic && self.amount.is_none()
+    }
+
+    pub fn decode_qr(qr: &str) -> Result<Qris, Error> {
+        let mut accounts = Vec::new();
+        let mut repository = None;
+        let mut point_of_initiation = None;
+        let mut merchant_category_code = None;
+        let mut currency = None;
+        let mut country = None;
+        let mut amount = None;
+        let mut merchant_name = None;
+        let mut merchant_city = None;
+        let mut postal_code = None;
+        let mut additional_data_template = None;
+
+        for (tag, value) in split_payload(qr)? {
+            match tag {
+                1 => point_of_initiation = Some(value),
+                26..=45 => accounts.push(QrisAccount::from_template(tag, value)?),
+                NMID_TAG => repository = Some(value),
+                52 => merchant_category_code = Some(value.to_string()),
+                53 => currency = Some(value),
+                54 => amount = Some(parse_amount(value)?),
+                58 => country = Some(value),
+                59 => merchant_name = Some(value.to_string()),
+                60 => merchant_city = Some(value.to_string()),
+                tag if tag == Tags::PostalCode.code() => postal_code = Some(value.to_string()),
+                62 => additional_data_template = Some(AdditionalDataTemplate::from_string(value)?),
+                _ => {}
+            }
+        }
+
+        if currency != Some(IDR_CURRENCY_CODE) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "QRIS currency must be 360 (IDR)",
+            ));
+        }
+        if country != Some(CountryCode::ID.code()) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "QRIS country code must be ID",
+            ));
+        }
+        let dynamic = decode_dynamic(point_of_initiation, amount.is_some())?;
+
+        let repository = repository.ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                "Missing QRIS repository template (tag 51)",
+            )
+        })?;
+        let (nmid, criteria) = parse_repository(repository)?;
+
+        let missing = |name: &str, tag: u8| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Missing QRIS {} (tag {})", name, tag),
+            )
+        };
+        let qris = Qris {
+            accounts,
+            nmid,
+            criteria,
+            merchant_category_code: merchant_category_code
+                .ok_or_else(|| missing("merchant category code", 52))?,
+            dynamic,
+            amount,
+            merchant_name: merchant_name.ok_or_else(|| missing("merchant name", 59))?,
+            merchant_city: merchant_city.ok_or_else(|| missing("merchant city", 60))?,
+            postal_code: postal_code
+                .ok_or_else(|| missing("postal code", Tags::PostalCode.code()))?,
+            additional_data_template,
+        };
+        qris.validate()?;
+        Ok(qris)
+    }
+
+    pub fn generate_qr(&self) -> Result<String, Error> {
+        let mut qr_code = String::with_capacity(256);
+        self.write_qr(&mut qr_code)?;
+        Ok(qr_code)
+    }
+
+    /// Encodes the payload in canonical order: ascending tags with tag 63 last.
+    pub fn write_qr<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.validate()?;
+
+        let mut qr_code = CrcWriter::new(writer);
+        write_payload_header(&mut qr_code, self.is_static())?;
+
+        let mut accounts: Vec<&QrisAccount> = self.accounts.iter().collect();
+        accounts.sort_by_key(|account| account.tag);
+        for account in accounts {
+            account.to_tlv(&mut qr_code)?;
+        }
+
+        let mut repository = String::new();
+        write_tlv!(repository, (0, QRIS_GUID)).map_err(Error::other)?;
+        write_tlv!(repository, (2, self.nmid)).map_err(Error::other)?;
+        write_tlv!(repository, (3, self.criteria.code())).map_err(Error::other)?;
+        write_tlv!(&mut qr_code, (NMID_TAG, repository)).map_err(Error::other)?;
+
+        write_tlv!(
+            &mut qr_code,
+            (
+                Tags::MerchantCategoryCode.code(),
+                self.merchant_category_code
+            )
+        )
+        .map_err(Error::other)?;
+        write_tlv!(
+            &mut qr_code,
+            (Tags::TransactionCurrency.code(), IDR_CURRENCY_CODE)
+        )
+        .map_err(Error::other)?;
+        if let Some(amount) = self.amount {
+            let amount = amount.to_string();
+            Tags::TransactionAmount
+                .validate_length(&amount)
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+            write_tlv!(&mut qr_code, (Tags::TransactionAmount.code(), amount))
+                .map_err(Error::other)?;
+        }
+        write_tlv!(
+            &mut qr_code,
+            (Tags::CountryCode.code(), CountryCode::ID.code())
+        )
+        .map_err(Error::other)?;
+        write_tlv!(
+            &mut qr_code,
+            (Tags::MerchantName.code(), self.merchant_name)
+        )
+        .map_err(Error::other)?;
+        write_tlv!(
+            &mut qr_code,
+            (Tags::MerchantCity.code(), self.merchant_city)
+        )
+        .map_err(Error::other)?;
+        write_tlv!(&mut qr_code, (Tags::PostalCode.code(), self.postal_code))
+            .map_err(Error::other)?;
+        if let Some(additional_data_template) = &self.additional_data_template {
+            additional_data_template.to_tlv(&mut qr_code)?;
+        }
+
+        qr_code.finish()
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if self.accounts.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "QRIS requires at least one issuer template (tags 26 to 45)",
+            ));
+        }
+        let mut seen = SeenTags::default();
+        for account in &self.accounts {
+            if !seen.insert(account.tag) {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Duplicate tag '{}'", account.tag),
+                ));
+            }
+            account.validate()?;
+        }
+
+        let nmid_digits = self.nmid.strip_prefix("ID").unwrap_or_default();
+        if !(10..=15).contains(&nmid_digits.len())
+            || !nmid_digits.bytes().all(|b| b.is_ascii_digit())
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "QRIS NMID must be 'ID' followed by 10 to 15 digits",
+            ));
+        }
+
+        Tags::MerchantCategoryCode
+            .validate_length(&self.merchant_category_code)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+        if self.amount == Some(0) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "QRIS amount must be positive",
+            ));
+        }
+        if self.merchant_name.is_empty() || self.merchant_city.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "QRIS requires a merchant name and city",
+            ));
+        }
+        Tags::MerchantName
+            .validate_length(&self.merchant_name)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+        Tags::MerchantCity
+            .validate_length(&self.merchant_city)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+        if self.postal_code.len() != 5 || !self.postal_code.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "QRIS postal code must be exactly 5 digits",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Parses a rupiah amount, which generators write either whole or with zero decimals.
+fn parse_amount(value: &str) -> Result<u64, Error> {
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    if !fraction.bytes().all(|b| b == b'0') {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "QRIS amount must be a whole number of rupiah",
+        ));
+    }
+    whole
+        .parse()
+        .map_err(|_| Error::new(ErrorKind::InvalidData, "Invalid QRIS amount"))
+}
+
+fn parse_repository(value: &str) -> Result<(String, QrisMerchantCriteria), Error> {
+    let mut guid = None;
+    let mut nmid = None;
+    let mut criteria = None;
+    let mut seen = SeenTags::default();
+
+    for (sub_tag, sub_value) in split_tlv(value)? {
+        if !seen.insert(sub_tag) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("Duplicate QRIS sub-tag 51.{:02}", sub_tag),
+            ));
+        }
+        match sub_tag {
+            0 => guid = Some(sub_value),
+            2 => nmid = Some(sub_value.to_string()),
+            3 => criteria = Some(QrisMerchantCriteria::parse(sub_value, "51.03")?),
+            _ => {}
+        }
+    }
+
+    if guid != Some(QRIS_GUID) {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("Tag 51 must carry the QRIS GUID {}", QRIS_GUID),
+        ));
+    }
+    let nmid =
+        nmid.ok_or_else(|| Error::new(ErrorKind::InvalidData, "Missing QRIS NMID (51.02)"))?;
+    let criteria = criteria.ok_or_else(|| {
+        Error::new(
+            ErrorKind::InvalidData,
+            "Missing QRIS merchant criteria (51.03)",
+        )
+    })?;
+    Ok((nmid, criteria))
+}
@@ -1,4 +1,6 @@
 use super::Tags;
+use alloc::format;
+use alloc::string::{String, ToString};
 
 #[derive(Debug)]
 pub enum SubTags {
@@ -45,7 +47,9 @@ impl SubTags {
         match (tag, code) {
             (Tags::MerchantAccountInfoIndividual, 0) => Some(Self::BakongAccountIdentifier),
             (Tags::MerchantAccountInfoIndividual, 1) => Some(Self::IndividualAccountInformation),
+            (Tags::MerchantAccountInfoIndividual, 2) => Some(Self::AcquiringBank),
 
+            (Tags::MerchantAccountInfoMerchant, 0) => Some(Self::BakongAccountIdentifier),
             (Tags::MerchantAccountInfoMerchant, 1) => Some(Self::MerchantIdentifier),
             (Tags::MerchantAccountInfoMerchant, 2) => Some(Self::AcquiringBank),
 
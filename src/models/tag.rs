@@ -1,3 +1,5 @@
+use alloc::format;
+use alloc::string::{String, ToString};
 #[derive(Debug)]
 pub enum Tags {
     PayloadFormatIndicator,
@@ -7,14 +9,24 @@ pub enum Tags {
     MerchantCategoryCode,
     TransactionCurrency,
     TransactionAmount,
+    TipOrConvenienceIndicator,
+    ConvenienceFeeFixed,
+    ConvenienceFeePercentage,
     CountryCode,
     MerchantName,
     MerchantCity,
+    PostalCode,
     AdditionalDataTemplate,
     AdditionalDataField,
     Crc,
     UnionPayMerchant,
     MerchantInformationLanguageTemplate,
+    /// Merchant account templates 26–51 other than Bakong's 29 and 30, e.g. other national schemes.
+    MerchantAccountInformation(u8),
+    /// Card network merchant accounts 02–14 and 16–25 (Visa, Mastercard, Amex, JCB, ...).
+    NetworkMerchantAccount(u8),
+    /// Unreserved templates 80–98: a GUID in sub-tag 00 plus proprietary sub-tags.
+    UnreservedTemplate(u8),
 }
 
 impl Tags {
@@ -27,14 +39,21 @@ impl Tags {
             Self::MerchantCategoryCode => 52,
             Self::TransactionCurrency => 53,
             Self::TransactionAmount => 54,
+            Self::TipOrConvenienceIndicator => 55,
+            Self::ConvenienceFeeFixed => 56,
+            Self::ConvenienceFeePercentage => 57,
             Self::CountryCode => 58,
             Self::MerchantName => 59,
             Self::MerchantCity => 60,
+            Self::PostalCode => 61,
             Self::AdditionalDataTemplate => 62,
             Self::AdditionalDataField => 99,
             Self::Crc => 63,
             Self::UnionPayMerchant => 15,
             Self::MerchantInformationLanguageTemplate => 64,
+            Self::MerchantAccountInformation(code) => *code,
+            Self::NetworkMerchantAccount(code) => *code,
+            Self::UnreservedTemplate(code) => *code,
         }
     }
 
@@ -47,14 +66,21 @@ impl Tags {
             52 => Some(Self::MerchantCategoryCode),
             53 => Some(Self::TransactionCurrency),
             54 => Some(Self::TransactionAmount),
+            55 => Some(Self::TipOrConvenienceIndicator),
+            56 => Some(Self::ConvenienceFeeFixed),
+            57 => Some(Self::ConvenienceFeePercentage),
             58 => Some(Self::CountryCode),
             59 => Some(Self::MerchantName),
             60 => Some(Self::MerchantCity),
+            61 => Some(Self::PostalCode),
             62 => Some(Self::AdditionalDataTemplate),
             99 => Some(Self::AdditionalDataField),
             63 => Some(Self::Crc),
             15 => Some(Self::UnionPayMerchant),
             64 => Some(Self::MerchantInformationLanguageTemplate),
+            26..=51 => Some(Self::MerchantAccountInformation(number)),
+            2..=14 | 16..=25 => Some(Self::NetworkMerchantAccount(number)),
+            80..=98 => Some(Self::UnreservedTemplate(number)),
             _ => None,
         }
     }
@@ -70,12 +96,19 @@ impl Tags {
             Self::MerchantAccountInfoMerchant => 99,
             Self::MerchantCategoryCode => 4,
             Self::TransactionAmount => 14,
+            Self::TipOrConvenienceIndicator => 2,
+            Self::ConvenienceFeeFixed => 13,
+            Self::ConvenienceFeePercentage => 5,
             Self::MerchantName => 25,
             Self::MerchantCity => 15,
+            Self::PostalCode => 10,
             Self::AdditionalDataTemplate => 99,
             Self::AdditionalDataField => 99,
             Self::UnionPayMerchant => 99,
             Self::MerchantInformationLanguageTemplate => 99,
+            Self::MerchantAccountInformation(_) => 99,
+            Self::NetworkMerchantAccount(_) => 99,
+            Self::UnreservedTemplate(_) => 99,
         }
     }
 
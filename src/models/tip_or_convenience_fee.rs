@@ -0,0 +1,257 @@
+use crate::error::{Error, ErrorKind};
+use crate::models::{Tags, TransactionAmount, TransactionCurrency};
+use crate::tlv::SliceWriter;
+use crate::write_tlv;
+use alloc::format;
+use core::fmt::Write;
+
+/// Tag 55.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "python", pyo3::pyclass(eq, eq_int, from_py_object))]
+pub enum TipOrConvenienceIndicator {
+    /// `01`: the consumer's app prompts for a tip.
+    PromptForTip,
+    /// `02`: a fixed convenience fee, carried in tag 56.
+    FixedFee,
+    /// `03`: a percentage convenience fee, carried in tag 57.
+    PercentageFee,
+}
+
+impl TipOrConvenienceIndicator {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::PromptForTip => "01",
+            Self::FixedFee => "02",
+            Self::PercentageFee => "03",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "01" => Some(Self::PromptForTip),
+            "02" => Some(Self::FixedFee),
+            "03" => Some(Self::PercentageFee),
+            _ => None,
+        }
+    }
+}
+
+/// Tip or convenience fee (tags 55–57). Exactly the value the indicator calls for must be set:
+/// none for a tip prompt, `fixed_fee` for a fixed fee and `percentage_fee` for a percentage.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(
+    feature = "python",
+    pyo3::pyclass(get_all, set_all, eq, from_py_object)
+)]
+pub struct TipOrConvenienceFee {
+    pub indicator: TipOrConvenienceIndicator,
+    /// Tag 56, in the QR's currency.
+    pub fixed_fee: Option<TransactionAmount>,
+    /// Tag 57, from 0.01 to 99.99 percent of the amount.
+    pub percentage_fee: Option<f32>,
+}
+
+impl TipOrConvenienceFee {
+    pub fn prompt_for_tip() -> Self {
+        Self {
+            indicator: TipOrConvenienceIndicator::PromptForTip,
+            fixed_fee: None,
+            percentage_fee: None,
+        }
+    }
+
+    pub fn fixed(fee: TransactionAmount) -> Self {
+        Self {
+            indicator: TipOrConvenienceIndicator::FixedFee,
+            fixed_fee: Some(fee),
+            percentage_fee: None,
+        }
+    }
+
+    pub fn percentage(percentage: f32) -> Self {
+        Self {
+            indicator: TipOrConvenienceIndicator::PercentageFee,
+            fixed_fee: None,
+            percentage_fee: Some(percentage),
+        }
+    }
+
+    /// Builds the fee from the raw values of tags 55, 56 and 57 of a QR in `currency`.
+    pub fn from_tags(
+        indicator: Option<&str>,
+        fixed: Option<&str>,
+        percentage: Option<&str>,
+        currency: &TransactionCurrency,
+    ) -> Result<Option<Self>, Error> {
+        let Some(indicator) = indicator else {
+            if fixed.is_some() || percentage.is_some() {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "Convenience fee (tag 56 or 57) requires tag 55",
+                ));
+            }
+            return Ok(None);
+        };
+
+        let indicator = TipOrConvenienceIndicator::from_code(indicator).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Unknown tip or convenience indicator '{}'", indicator),
+            )
+        })?;
+        let fixed_fee = match fixed {
+            Some(value) => Some(parse_fee(currency, value)?),
+            None => None,
+        };
+        let percentage_fee = match percentage {
+            Some(value) => Some(value.parse::<f32>().map_err(|_| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    "Invalid convenience fee percentage (tag 57)",
+                )
+            })?),
+            None => None,
+        };
+
+        let fee = Self {
+            indicator,
+            fixed_fee,
+            percentage_fee,
+        };
+        fee.validate(currency)?;
+        Ok(Some(fee))
+    }
+
+    pub fn validate(&self, currency: &TransactionCurrency) -> Result<(), Error> {
+        match (&self.indicator, &self.fixed_fee, &self.percentage_fee) {
+            (TipOrConvenienceIndicator::PromptForTip, None, None) => Ok(()),
+            (TipOrConvenienceIndicator::PromptForTip, _, _) => Err(Error::new(
+                ErrorKind::InvalidData,
+                "Tip prompt (tag 55 = 01) must not carry tag 56 or 57",
+            )),
+            (TipOrConvenienceIndicator::FixedFee, Some(fee), None) => {
+                if fee.currency() != *currency {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!(
+                            "Convenience fee currency {:?} does not match the QR currency {:?}",
+                            fee.currency(),
+                            currency
+                        ),
+                    ));
+                }
+                let positive = match fee {
+                    TransactionAmount::KHR(fee) => *fee > 0,
+                    TransactionAmount::USD(fee)
+                    | TransactionAmount::THB(fee)
+                    | TransactionAmount::MYR(fee) => *fee > 0.0,
+                };
+                if !positive {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        "Convenience fee must be positive",
+                    ));
+                }
+                fee_value(fee, &mut [0u8; 16]).map(|_| ())
+            }
+            (TipOrConvenienceIndicator::FixedFee, _, _) => Err(Error::new(
+                ErrorKind::InvalidData,
+                "Fixed convenience fee (tag 55 = 02) requires tag 56 and not tag 57",
+            )),
+            (TipOrConvenienceIndicator::PercentageFee, None, Some(percentage)) => {
+                if !(0.01..=99.99).contains(percentage) {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!(
+                            "Convenience fee percentage must be 0.01 to 99.99, got {}",
+                            percentage
+                        ),
+                    ));
+                }
+                Ok(())
+            }
+            (TipOrConvenienceIndicator::PercentageFee, _, _) => Err(Error::new(
+                ErrorKind::InvalidData,
+                "Percentage convenience fee (tag 55 = 03) requires tag 57 and not tag 56",
+            )),
+        }
+    }
+
+    /// Writes tag 55 followed by tag 56 or 57 when the indicator calls for one.
+    pub fn write_tags<W: Write + ?Sized>(
+        &self,
+        writer: &mut W,
+        currency: &TransactionCurrency,
+    ) -> Result<(), Error> {
+        self.validate(currency)?;
+
+        write_tlv!(
+            writer,
+            (
+                Tags::TipOrConvenienceIndicator.code(),
+                self.indicator.code()
+            )
+        )
+        .map_err(Error::other)?;
+        if let Some(fee) = &self.fixed_fee {
+            let mut buffer = [0u8; 16];
+            let fee = fee_value(fee, &mut buffer)?;
+            write_tlv!(writer, (Tags::ConvenienceFeeFixed.code(), fee)).map_err(Error::other)?;
+        }
+        if let Some(percentage) = self.percentage_fee {
+            // `validate` bounds the percentage to 0.01–99.99, so it always fits.
+            let mut buffer = [0u8; 8];
+            let mut value = SliceWriter::new(&mut buffer);
+            write!(value, "{:.2}", percentage).map_err(Error::other)?;
+            write_tlv!(
+                writer,
+                (Tags::ConvenienceFeePercentage.code(), value.as_str())
+            )
+            .map_err(Error::other)?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats the tag 56 value into `buffer`, the way `TransactionAmount::write_amount` does.
+fn fee_value<'a>(fee: &TransactionAmount, buffer: &'a mut [u8; 16]) -> Result<&'a str, Error> {
+    let too_long = || {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "{:?} exceeds max length {}",
+                Tags::ConvenienceFeeFixed,
+                Tags::ConvenienceFeeFixed.max_length()
+            ),
+        )
+    };
+    let mut value = SliceWriter::new(buffer);
+    write!(value, "{}", fee).map_err(|_| too_long())?;
+    let value = value.into_str();
+    Tags::ConvenienceFeeFixed
+        .validate_length(value)
+        .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+    Ok(value)
+}
+
+fn parse_fee(currency: &TransactionCurrency, value: &str) -> Result<TransactionAmount, Error> {
+    let invalid = || Error::new(ErrorKind::InvalidData, "Invalid convenience fee (tag 56)");
+    match currency {
+        TransactionCurrency::KHR => Ok(TransactionAmount::KHR(
+            value.parse().map_err(|_| invalid())?,
+        )),
+        TransactionCurrency::USD => Ok(TransactionAmount::USD(
+            value.parse().map_err(|_| invalid())?,
+        )),
+        TransactionCurrency::THB => Ok(TransactionAmount::THB(
+            value.parse().map_err(|_| invalid())?,
+        )),
+        TransactionCurrency::MYR => Ok(TransactionAmount::MYR(
+            value.parse().map_err(|_| invalid())?,
+        )),
+    }
+}
@@ -1,33 +1,43 @@
+use crate::error::{Error, ErrorKind};
 use crate::models::Tags;
+use crate::tlv::SliceWriter;
 use crate::write_tlv;
-use std::fmt::{Display, Write};
-use std::io::{Error, ErrorKind};
+use alloc::format;
+use core::fmt::{Display, Write};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "python", pyo3::pyclass(eq, eq_int, from_py_object))]
 pub enum TransactionCurrency {
     KHR,
     USD,
+    /// Thai baht, carried by PromptPay payloads; Bakong rejects it.
+    THB,
+    /// Malaysian ringgit, carried by DuitNow payloads; Bakong rejects it.
+    MYR,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "python", pyo3::pyclass(eq, from_py_object))]
 pub enum TransactionAmount {
     KHR(u32),
     USD(f32),
+    THB(f32),
+    MYR(f32),
 }
 impl TransactionCurrency {
-    pub fn write_currency(&self, buffer: &mut String) -> Result<(), Error> {
+    pub fn write_currency<W: Write + ?Sized>(&self, buffer: &mut W) -> Result<(), Error> {
         let code = self.currency_code();
 
         Tags::TransactionCurrency
             .validate_length(code)
             .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
 
-        write_tlv!(buffer, (Tags::TransactionCurrency.code(), code)).map_err(|e| {
-            Error::new(
-                ErrorKind::Other,
-                format!("Failed to write currency TLV: {}", e),
-            )
-        })?;
+        write_tlv!(buffer, (Tags::TransactionCurrency.code(), code))
+            .map_err(|e| Error::other(format!("Failed to write currency TLV: {}", e)))?;
 
         Ok(())
     }
@@ -36,6 +46,8 @@ impl TransactionCurrency {
         match value {
             "116" => Ok(TransactionCurrency::KHR),
             "840" => Ok(TransactionCurrency::USD),
+            "764" => Ok(TransactionCurrency::THB),
+            "458" => Ok(TransactionCurrency::MYR),
             _ => Err(Error::new(ErrorKind::InvalidData, "Invalid currency code")),
         }
     }
@@ -44,12 +56,21 @@ impl TransactionCurrency {
         match self {
             Self::KHR => "116",
             Self::USD => "840",
+            Self::THB => "764",
+            Self::MYR => "458",
         }
     }
+
+    /// Whether a Bakong (KHQR) payload may use this currency.
+    pub fn is_bakong(&self) -> bool {
+        matches!(self, Self::KHR | Self::USD)
+    }
 }
 
 impl TransactionAmount {
-    pub fn write_amount(&self, temp_value: &mut String, value: &mut String) -> Result<(), Error> {
+    pub fn write_amount<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        let mut buffer = [0u8; 16];
+        let mut amount_value = SliceWriter::new(&mut buffer);
         match self {
             Self::KHR(amount) => {
                 if *amount < 100 {
@@ -58,12 +79,6 @@ impl TransactionAmount {
                         format!("KHR amount must be at least 100, got {}", amount),
                     ));
                 }
-                write!(temp_value, "{}", amount).map_err(|e| {
-                    Error::new(
-                        ErrorKind::Other,
-                        format!("Failed to format KHR amount: {}", e),
-                    )
-                })?;
             }
             Self::USD(amount) => {
                 if *amount < 0.1 {
@@ -72,43 +87,53 @@ impl TransactionAmount {
                         format!("USD amount must be at least 0.1, got {}", amount),
                     ));
                 }
-                write!(temp_value, "{:.2}", amount).map_err(|e| {
-                    Error::new(
-                        ErrorKind::Other,
-                        format!("Failed to format USD amount: {}", e),
-                    )
-                })?;
+            }
+            Self::THB(amount) | Self::MYR(amount) => {
+                if !amount.is_finite() || *amount < 0.01 {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!(
+                            "{:?} amount must be at least 0.01, got {}",
+                            self.currency(),
+                            amount
+                        ),
+                    ));
+                }
             }
         };
+        write!(amount_value, "{}", self).map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "{:?} exceeds max length {}",
+                    Tags::TransactionAmount,
+                    Tags::TransactionAmount.max_length()
+                ),
+            )
+        })?;
 
         Tags::TransactionAmount
-            .validate_length(temp_value)
+            .validate_length(amount_value.as_str())
             .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
 
-        write_tlv!(value, (Tags::TransactionAmount.code(), temp_value)).map_err(|e| {
-            Error::new(
-                ErrorKind::Other,
-                format!("Failed to write TransactionAmount TLV: {}", e),
-            )
-        })?;
+        write_tlv!(
+            writer,
+            (Tags::TransactionAmount.code(), amount_value.as_str())
+        )
+        .map_err(|e| Error::other(format!("Failed to write TransactionAmount TLV: {}", e)))?;
 
-        temp_value.clear();
         Ok(())
     }
 
-    pub fn write_currency(&self, buffer: &mut String) -> Result<(), Error> {
+    pub fn write_currency<W: Write + ?Sized>(&self, buffer: &mut W) -> Result<(), Error> {
         let code = self.currency_code();
 
         Tags::TransactionCurrency
             .validate_length(code)
             .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
 
-        write_tlv!(buffer, (Tags::TransactionCurrency.code(), code)).map_err(|e| {
-            Error::new(
-                ErrorKind::Other,
-                format!("Failed to write currency TLV: {}", e),
-            )
-        })?;
+        write_tlv!(buffer, (Tags::TransactionCurrency.code(), code))
+            .map_err(|e| Error::other(format!("Failed to write currency TLV: {}", e)))?;
 
         Ok(())
     }
@@ -146,23 +171,55 @@ impl TransactionAmount {
 
                 Ok(TransactionAmount::USD(parsed))
             }
+            TransactionCurrency::THB | TransactionCurrency::MYR => {
+                let parsed = value.parse::<f32>().map_err(|_| {
+                    Error::new(
+                        ErrorKind::InvalidData,
+                        format!("Invalid {:?} amount", currency),
+                    )
+                })?;
+
+                if !parsed.is_finite() || parsed < 0.01 {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!(
+                            "{:?} amount must be at least 0.01, got {}",
+                            currency, parsed
+                        ),
+                    ));
+                }
+
+                Ok(match currency {
+                    TransactionCurrency::THB => TransactionAmount::THB(parsed),
+                    _ => TransactionAmount::MYR(parsed),
+                })
+            }
         }
     }
 
     #[inline]
-    pub fn currency_code(&self) -> &'static str {
+    pub fn currency(&self) -> TransactionCurrency {
         match self {
-            Self::KHR(_) => TransactionCurrency::KHR.currency_code(),
-            Self::USD(_) => TransactionCurrency::USD.currency_code(),
+            Self::KHR(_) => TransactionCurrency::KHR,
+            Self::USD(_) => TransactionCurrency::USD,
+            Self::THB(_) => TransactionCurrency::THB,
+            Self::MYR(_) => TransactionCurrency::MYR,
         }
     }
+
+    #[inline]
+    pub fn currency_code(&self) -> &'static str {
+        self.currency().currency_code()
+    }
 }
 
 impl Display for TransactionAmount {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::KHR(amount) => write!(f, "{}", amount),
-            Self::USD(amount) => write!(f, "{:.2}", amount),
+            Self::USD(amount) | Self::THB(amount) | Self::MYR(amount) => {
+                write!(f, "{:.2}", amount)
+            }
         }
     }
 }
@@ -0,0 +1,128 @@
+use crate::error::{Error, ErrorKind};
+use crate::{TLV, models::Tags};
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt::Write;
+
+const IIN_LENGTH: usize = 8;
+const MERCHANT_ID_MAX_LENGTH: usize = 15;
+
+/// UnionPay merchant account (tag 15): the acquirer IIN followed by the merchant ID.
+///
+/// Values that do not follow that layout are kept as `Raw`, so payloads issued before the typed
+/// form still decode and re-encode unchanged. With `serde`, `Raw` is a plain string.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "python", pyo3::pyclass(eq, from_py_object))]
+pub enum UnionPayMerchant {
+    Account {
+        /// 8-digit institution identification number of the acquirer.
+        acquirer_iin: String,
+        /// Merchant ID assigned by the acquirer, up to 15 letters or digits.
+        merchant_id: String,
+    },
+    Raw(String),
+}
+
+impl UnionPayMerchant {
+    pub fn new(acquirer_iin: String, merchant_id: String) -> Result<Self, Error> {
+        let merchant = Self::Account {
+            acquirer_iin,
+            merchant_id,
+        };
+        merchant.validate()?;
+        Ok(merchant)
+    }
+
+    /// Reads a tag 15 value, falling back to `Raw` when it is not an IIN plus merchant ID.
+    pub fn from_string(value: &str) -> Self {
+        if let (Some(acquirer_iin), Some(merchant_id)) =
+            (value.get(..IIN_LENGTH), value.get(IIN_LENGTH..))
+        {
+            let account = Self::Account {
+                acquirer_iin: acquirer_iin.to_string(),
+                merchant_id: merchant_id.to_string(),
+            };
+            if account.validate().is_ok() {
+                return account;
+            }
+        }
+        Self::Raw(value.to_string())
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        match self {
+            Self::Account {
+                acquirer_iin,
+                merchant_id,
+            } => {
+                if acquirer_iin.len() != IIN_LENGTH
+                    || !acquirer_iin.bytes().all(|b| b.is_ascii_digit())
+                {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!(
+                            "UnionPay acquirer IIN must be exactly {} digits",
+                            IIN_LENGTH
+                        ),
+                    ));
+                }
+                if merchant_id.is_empty()
+                    || merchant_id.len() > MERCHANT_ID_MAX_LENGTH
+                    || !merchant_id.bytes().all(|b| b.is_ascii_alphanumeric())
+                {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!(
+                            "UnionPay merchant ID must be 1 to {} letters or digits",
+                            MERCHANT_ID_MAX_LENGTH
+                        ),
+                    ));
+                }
+                Ok(())
+            }
+            Self::Raw(value) => {
+                if value.is_empty() {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        "UnionPayMerchant must not be empty",
+                    ));
+                }
+                Tags::UnionPayMerchant
+                    .validate_length(value)
+                    .map_err(|e| Error::new(ErrorKind::InvalidData, e))
+            }
+        }
+    }
+}
+
+impl From<String> for UnionPayMerchant {
+    fn from(value: String) -> Self {
+        Self::from_string(&value)
+    }
+}
+
+impl From<&str> for UnionPayMerchant {
+    fn from(value: &str) -> Self {
+        Self::from_string(value)
+    }
+}
+
+impl TLV for UnionPayMerchant {
+    fn tag(&self) -> Tags {
+        Tags::UnionPayMerchant
+    }
+
+    fn write_value<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.validate()?;
+        match self {
+            Self::Account {
+                acquirer_iin,
+                merchant_id,
+            } => write!(writer, "{}{}", acquirer_iin, merchant_id).map_err(Error::other),
+            Self::Raw(value) => writer.write_str(value).map_err(Error::other),
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use crate::error::{Error, ErrorKind};
+use crate::extension::Extensions;
+use crate::models::Tags;
+use crate::tlv::{SeenTags, TLV, split_tlv};
+use crate::write_tlv;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+const GUID_SUB_TAG: u8 = 0;
+const GUID_MAX_LENGTH: usize = 32;
+
+/// A proprietary sub-tag (01–99) of an unreserved template.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(
+    feature = "python",
+    pyo3::pyclass(get_all, set_all, eq, from_py_object)
+)]
+pub struct UnreservedField {
+    pub tag: u8,
+    pub value: String,
+}
+
+/// Unreserved template (tags 80–98): the GUID of the party that defines it in sub-tag 00,
+/// followed by that party's own sub-tags, written in ascending order.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(
+    feature = "python",
+    pyo3::pyclass(get_all, set_all, eq, from_py_object)
+)]
+pub struct UnreservedTemplate {
+    pub tag: u8,
+    /// An AID or reverse domain name, up to 32 characters.
+    pub guid: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fields: Vec<UnreservedField>,
+}
+
+impl UnreservedTemplate {
+    pub fn new(tag: u8, guid: String, fields: Vec<UnreservedField>) -> Result<Self, Error> {
+        let template = Self { tag, guid, fields };
+        template.validate()?;
+        Ok(template)
+    }
+
+    pub fn from_string(tag: u8, value: &str) -> Result<Self, Error> {
+        let mut guid = None;
+        let mut fields = Vec::new();
+        let mut seen = SeenTags::default();
+
+        for (sub_tag, sub_value) in split_tlv(value)? {
+            if !seen.insert(sub_tag) {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Duplicate sub-tag {:02}.{:02}", tag, sub_tag),
+                ));
+            }
+            if sub_tag == GUID_SUB_TAG {
+                guid = Some(sub_value.to_string());
+            } else {
+                fields.push(UnreservedField {
+                    tag: sub_tag,
+                    value: sub_value.to_string(),
+                });
+            }
+        }
+
+        let guid = guid.ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Missing GUID (sub-tag 00) in tag {:02}", tag),
+            )
+        })?;
+        Self::new(tag, guid, fields)
+    }
+
+    /// Value of proprietary sub-tag `tag`.
+    pub fn field(&self, tag: u8) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|field| field.tag == tag)
+            .map(|field| field.value.as_str())
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        if !(80..=98).contains(&self.tag) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("Tag {:02} is not an unreserved template (80-98)", self.tag),
+            ));
+        }
+        if self.guid.is_empty()
+            || self.guid.len() > GUID_MAX_LENGTH
+            || !self.guid.bytes().all(|b| b.is_ascii_graphic())
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "GUID in tag {:02} must be 1 to {} printable characters",
+                    self.tag, GUID_MAX_LENGTH
+                ),
+            ));
+        }
+
+        let mut seen = SeenTags::default();
+        for field in &self.fields {
+            if field.tag == GUID_SUB_TAG || field.tag > 99 {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Sub-tag {:02}.{:02} must be 01 to 99", self.tag, field.tag),
+                ));
+            }
+            if !seen.insert(field.tag) {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Duplicate sub-tag {:02}.{:02}", self.tag, field.tag),
+                ));
+            }
+            if field.value.len() > 99 {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!(
+                        "Sub-tag {:02}.{:02} exceeds max length 99",
+                        self.tag, field.tag
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TLV for UnreservedTemplate {
+    fn tag(&self) -> Tags {
+        Tags::UnreservedTemplate(self.tag)
+    }
+
+    fn write_value<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.write_sub_tags(writer, |_, _| Ok(false))
+    }
+}
+
+impl UnreservedTemplate {
+    /// Writes the GUID and then sub-tags 01–99 in ascending order. `extra` writes a sub-tag
+    /// the template does not carry itself and reports whether it wrote one.
+    pub(crate) fn write_sub_tags<W: Write + ?Sized>(
+        &self,
+        writer: &mut W,
+        mut extra: impl FnMut(&mut W, u8) -> Result<bool, Error>,
+    ) -> Result<(), Error> {
+        self.validate()?;
+
+        write_tlv!(writer, (GUID_SUB_TAG, &self.guid)).map_err(Error::other)?;
+        for sub_tag in 1..=99 {
+            let own = self.field(sub_tag);
+            if let Some(value) = own {
+                write_tlv!(writer, (sub_tag, value)).map_err(Error::other)?;
+            }
+            if extra(writer, sub_tag)? && own.is_some() {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Duplicate sub-tag {:02}.{:02}", self.tag, sub_tag),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An unreserved template written with extension sub-tags merged in, without copying
+/// the template.
+pub(crate) struct MergedTemplate<'a> {
+    pub(crate) template: &'a UnreservedTemplate,
+    pub(crate) extensions: &'a Extensions,
+}
+
+impl TLV for MergedTemplate<'_> {
+    fn tag(&self) -> Tags {
+        self.template.tag()
+    }
+
+    fn write_value<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.template.write_sub_tags(writer, |writer, sub_tag| {
+            self.extensions
+                .write_sub_field(writer, self.template.tag, sub_tag)
+        })
+    }
+}
+
+/// Maps the GUIDs of unreserved templates to the party that owns them. The owners of tags 80–98
+/// are agreed between partners, so the registry starts empty and the caller registers the GUIDs
+/// it knows.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GuidRegistry {
+    entries: Vec<(String, String)>,
+}
+
+impl GuidRegistry {
+    /// Records `owner` for `guid`, replacing any previous owner. GUIDs compare case-insensitively.
+    pub fn register(&mut self, guid: impl Into<String>, owner: impl Into<String>) {
+        let (guid, owner) = (guid.into(), owner.into());
+        match self
+            .entries
+            .iter_mut()
+            .find(|(known, _)| known.eq_ignore_ascii_case(&guid))
+        {
+            Some(entry) => entry.1 = owner,
+            None => self.entries.push((guid, owner)),
+        }
+    }
+
+    pub fn owner(&self, guid: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(known, _)| known.eq_ignore_ascii_case(guid))
+            .map(|(_, owner)| owner.as_str())
+    }
+
+    /// Owner of the GUID in `template`.
+    pub fn owner_of(&self, template: &UnreservedTemplate) -> Option<&str> {
+        self.owner(&template.guid)
+    }
+}
@@ -0,0 +1,288 @@
+use crate::error::{Error, ErrorKind};
+use crate::models::{AdditionalDataTemplate, CountryCode, Tags};
+use crate::tlv::{
+    CrcWriter, SeenTags, TLV, decode_dynamic, split_payload, split_tlv, write_payload_header,
+};
+use crate::write_tlv;
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt::Write;
+
+/// NAPAS GUID carried in sub-tag 00 of tag 38.
+pub const VIETQR_GUID: &str = "A000000727";
+/// ISO 4217 numeric code of the Vietnamese dong.
+pub const VND_CURRENCY_CODE: &str = "704";
+
+const VIETQR_TAG: u8 = 38;
+const ACCOUNT_MAX_LENGTH: usize = 19;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VietQrService {
+    /// `QRIBFTTA`: transfer to a bank account.
+    ToAccount,
+    /// `QRIBFTTC`: transfer to a card number.
+    ToCard,
+}
+
+impl VietQrService {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::ToAccount => "QRIBFTTA",
+            Self::ToCard => "QRIBFTTC",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "QRIBFTTA" => Some(Self::ToAccount),
+            "QRIBFTTC" => Some(Self::ToCard),
+            _ => None,
+        }
+    }
+}
+
+/// A Vietnamese VietQR (NAPAS) payload. It is dynamic (`12`) when `dynamic` is set or it
+/// carries an amount in dong.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VietQr {
+    /// 6-digit acquirer BIN of the beneficiary bank.
+    pub bank_bin: String,
+    /// Account or card number, depending on `service`.
+    pub account: String,
+    pub service: VietQrService,
+    /// Tag 01 is `12` even without an amount, for a single-use QR the payer completes.
+    pub dynamic: bool,
+    pub amount: Option<u64>,
+    pub merchant_name: Option<String>,
+    pub merchant_city: Option<String>,
+    pub additional_data_template: Option<AdditionalDataTemplate>,
+}
+
+impl VietQr {
+    pub fn is_static(&self) -> bool {
+        !self.dynamic && self.amount.is_none()
+    }
+
+    pub fn decode_qr(qr: &str) -> Result<VietQr, Error> {
+        let mut template = None;
+        let mut point_of_initiation = None;
+        let mut currency = None;
+        let mut country = None;
+        let mut amount = None;
+        let mut merchant_name = None;
+        let mut merchant_city = None;
+        let mut additional_data_template = None;
+
+        for (tag, value) in split_payload(qr)? {
+            match tag {
+                1 => point_of_initiation = Some(value),
+                VIETQR_TAG => template = Some(value),
+                53 => currency = Some(value),
+                54 => {
+                    amount = Some(value.parse::<u64>().map_err(|_| {
+                        Error::new(
+                            ErrorKind::InvalidData,
+                            "VietQR amount must be a whole number of dong",
+                        )
+                    })?)
+                }
+                58 => country = Some(value),
+                59 => merchant_name = Some(value.to_string()),
+                60 => merchant_city = Some(value.to_string()),
+                62 => additional_data_template = Some(AdditionalDataTemplate::from_string(value)?),
+                _ => {}
+            }
+        }
+
+        let template = template.ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                "Missing VietQR account template (tag 38)",
+            )
+        })?;
+        if currency != Some(VND_CURRENCY_CODE) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "VietQR currency must be 704 (VND)",
+            ));
+        }
+        if country != Some(CountryCode::VN.code()) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "VietQR country code must be VN",
+            ));
+        }
+        let dynamic = decode_dynamic(point_of_initiation, amount.is_some())?;
+
+        let (bank_bin, account, service) = parse_template(template)?;
+        let vietqr = VietQr {
+            bank_bin,
+            account,
+            service,
+            dynamic,
+            amount,
+            merchant_name,
+            merchant_city,
+            additional_data_template,
+        };
+        vietqr.validate()?;
+        Ok(vietqr)
+    }
+
+    pub fn generate_qr(&self) -> Result<String, Error> {
+        let mut qr_code = String::with_capacity(128);
+        self.write_qr(&mut qr_code)?;
+        Ok(qr_code)
+    }
+
+    /// Encodes the payload in canonical order: ascending tags with tag 63 last.
+    pub fn write_qr<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.validate()?;
+
+        let mut qr_code = CrcWriter::new(writer);
+        write_payload_header(&mut qr_code, self.is_static())?;
+
+        let mut beneficiary = String::new();
+        write_tlv!(beneficiary, (0, self.bank_bin)).map_err(Error::other)?;
+        write_tlv!(beneficiary, (1, self.account)).map_err(Error::other)?;
+        let mut template = String::new();
+        write_tlv!(template, (0, VIETQR_GUID)).map_err(Error::other)?;
+        write_tlv!(template, (1, beneficiary)).map_err(Error::other)?;
+        write_tlv!(template, (2, self.service.code())).map_err(Error::other)?;
+        Tags::MerchantAccountInformation(VIETQR_TAG)
+            .validate_length(&template)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+        write_tlv!(&mut qr_code, (VIETQR_TAG, template)).map_err(Error::other)?;
+
+        write_tlv!(
+            &mut qr_code,
+            (Tags::TransactionCurrency.code(), VND_CURRENCY_CODE)
+        )
+        .map_err(Error::other)?;
+        if let Some(amount) = self.amount {
+            let amount = amount.to_string();
+            Tags::TransactionAmount
+                .validate_length(&amount)
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+            write_tlv!(&mut qr_code, (Tags::TransactionAmount.code(), amount))
+                .map_err(Error::other)?;
+        }
+        write_tlv!(
+            &mut qr_code,
+            (Tags::CountryCode.code(), CountryCode::VN.code())
+        )
+        .map_err(Error::other)?;
+
+        if let Some(name) = &self.merchant_name {
+            Tags::MerchantName
+                .validate_length(name)
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+            write_tlv!(&mut qr_code, (Tags::MerchantName.code(), name)).map_err(Error::other)?;
+        }
+        if let Some(city) = &self.merchant_city {
+            Tags::MerchantCity
+                .validate_length(city)
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+            write_tlv!(&mut qr_code, (Tags::MerchantCity.code(), city)).map_err(Error::other)?;
+        }
+        if let Some(additional_data_template) = &self.additional_data_template {
+            additional_data_template.to_tlv(&mut qr_code)?;
+        }
+
+        qr_code.finish()
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if self.bank_bin.len() != 6 || !self.bank_bin.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "VietQR bank BIN must be exactly 6 digits",
+            ));
+        }
+        if self.account.is_empty()
+            || self.account.len() > ACCOUNT_MAX_LENGTH
+            || !self.account.bytes().all(|b| b.is_ascii_alphanumeric())
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "VietQR account must be 1 to {} letters or digits",
+                    ACCOUNT_MAX_LENGTH
+                ),
+            ));
+        }
+        if self.service == VietQrService::ToCard
+            && !self.account.bytes().all(|b| b.is_ascii_digit())
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "VietQR card number must contain only digits",
+            ));
+        }
+        if self.amount == Some(0) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "VietQR amount must be positive",
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn parse_template(value: &str) -> Result<(String, String, VietQrService), Error> {
+    fn fields(value: &str, context: &str) -> Result<[Option<String>; 3], Error> {
+        let mut fields: [Option<String>; 3] = Default::default();
+        let mut seen = SeenTags::default();
+        for (sub_tag, sub_value) in split_tlv(value)? {
+            if !seen.insert(sub_tag) {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Duplicate VietQR sub-tag {}.{:02}", context, sub_tag),
+                ));
+            }
+            if let Some(field) = fields.get_mut(usize::from(sub_tag)) {
+                *field = Some(sub_value.to_string());
+            }
+        }
+        Ok(fields)
+    }
+
+    let [guid, beneficiary, service] = fields(value, "38")?;
+    if guid.as_deref() != Some(VIETQR_GUID) {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("Tag 38 must carry the NAPAS GUID {}", VIETQR_GUID),
+        ));
+    }
+
+    let beneficiary = beneficiary.ok_or_else(|| {
+        Error::new(
+            ErrorKind::InvalidData,
+            "Missing VietQR beneficiary (sub-tag 38.01)",
+        )
+    })?;
+    let [bank_bin, account, _] = fields(&beneficiary, "38.01")?;
+    let bank_bin = bank_bin
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "Missing VietQR bank BIN (38.01.00)"))?;
+    let account = account
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "Missing VietQR account (38.01.01)"))?;
+
+    let service = match service.as_deref() {
+        Some(code) => VietQrService::from_code(code).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Unknown VietQR service code '{}'", code),
+            )
+        })?,
+        None => {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Missing VietQR service code (38.02)",
+            ));
+        }
+    };
+
+    Ok((bank_bin, account, service))
+}
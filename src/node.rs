@@ -0,0 +1,52 @@
+use crate::error::Error;
+use crate::models::Bakong;
+use crate::render;
+use crate::validation;
+use napi_derive::napi;
+use serde_json::{Value, json};
+
+fn ok(value: impl serde::Serialize) -> Value {
+    match serde_json::to_value(value) {
+        Ok(value) => json!({ "ok": true, "value": value }),
+        Err(e) => failure("Other", e.to_string()),
+    }
+}
+
+fn failure(kind: &str, message: String) -> Value {
+    json!({ "ok": false, "error": { "kind": kind, "message": message } })
+}
+
+fn from_error(e: Error) -> Value {
+    failure(&format!("{:?}", e.kind()), e.to_string())
+}
+
+/// Encodes `bakong` into a KHQR payload.
+#[napi(ts_args_type = "bakong: Bakong", ts_return_type = "KhqrResult<string>")]
+pub fn generate(bakong: Value) -> Value {
+    let bakong: Bakong = match serde_json::from_value(bakong) {
+        Ok(bakong) => bakong,
+        Err(e) => return failure("InvalidInput", e.to_string()),
+    };
+    bakong.generate_qr().map_or_else(from_error, ok)
+}
+
+/// Decodes a KHQR payload, checking its CRC.
+#[napi(ts_return_type = "KhqrResult<Bakong>")]
+pub fn decode(qr: String) -> Value {
+    Bakong::decode_qr(&qr).map_or_else(from_error, ok)
+}
+
+/// Lists every problem in a payload; a payload that fails to decode yields error findings
+/// rather than an exception.
+#[napi(ts_return_type = "Finding[]")]
+pub fn validate(qr: String) -> Value {
+    serde_json::to_value(validation::validate(&qr)).unwrap_or_else(
+        |e| json!([{ "severity": "Error", "path": null, "message": e.to_string() }]),
+    )
+}
+
+/// Renders a payload as an SVG document at least `size` pixels wide.
+#[napi(ts_return_type = "KhqrResult<string>")]
+pub fn render_svg(qr: String, size: u32) -> Value {
+    render::render_svg(&qr, size).map_or_else(from_error, ok)
+}
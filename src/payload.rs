@@ -0,0 +1,279 @@
+use crate::error::{Error, ErrorKind};
+use crate::models::{Crc16Ccitt, SubTags, Tags};
+use crate::tlv::{CrcWriter, is_template, split_tlv};
+use crate::write_tlv;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Write};
+use core::str::FromStr;
+
+const MAX_VALUE_LENGTH: usize = 99;
+
+/// A payload edited tag by tag, addressed by paths such as `"15"` or `"62.07"`.
+///
+/// Tags and the sub-tags of templates are kept in canonical order (ascending, tag 63 last), and
+/// tag 63 is recomputed whenever the payload is written out.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Payload {
+    fields: Vec<(u8, String)>,
+}
+
+impl Payload {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses `qr`, rejecting it if a template (26–51, 62, 64, 80–99) is not valid TLV or tag 63
+    /// is present but does not match the payload.
+    pub fn parse(qr: &str) -> Result<Self, Error> {
+        let mut payload = Self::new();
+        let mut has_crc = false;
+
+        for (tag, value) in split_tlv(qr)? {
+            if tag == Tags::Crc.code() {
+                has_crc = true;
+                continue;
+            }
+            if payload.field(tag).is_some() {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Duplicate tag {:02}", tag),
+                ));
+            }
+
+            let value = if is_template(tag) {
+                let sub_fields = split_tlv(value).map_err(|e| {
+                    Error::new(
+                        ErrorKind::InvalidData,
+                        format!("Malformed template {:02}: {}", tag, e),
+                    )
+                })?;
+                let mut sorted: Vec<(u8, String)> = Vec::with_capacity(sub_fields.len());
+                for (sub_tag, sub_value) in sub_fields {
+                    if sorted.iter().any(|(t, _)| *t == sub_tag) {
+                        return Err(Error::new(
+                            ErrorKind::InvalidData,
+                            format!("Duplicate sub-tag {:02}.{:02}", tag, sub_tag),
+                        ));
+                    }
+                    upsert(&mut sorted, sub_tag, sub_value.to_string());
+                }
+                join(&sorted)
+            } else {
+                value.to_string()
+            };
+            upsert(&mut payload.fields, tag, value);
+        }
+
+        if has_crc && !Crc16Ccitt::verify(qr) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "CRC does not match the payload",
+            ));
+        }
+
+        Ok(payload)
+    }
+
+    /// Returns the value at `path`, or `None` if it is absent or the path is malformed.
+    /// Tag 63 is not stored; it is only produced when the payload is written.
+    pub fn get(&self, path: &str) -> Option<&str> {
+        let (tag, sub_tag) = parse_path(path).ok()?;
+        let value = self.field(tag)?;
+        match sub_tag {
+            None => Some(value),
+            Some(sub_tag) => split_tlv(value)
+                .ok()?
+                .into_iter()
+                .find(|(t, _)| *t == sub_tag)
+                .map(|(_, v)| v),
+        }
+    }
+
+    /// Sets the value at `path`, creating the enclosing template if needed.
+    pub fn set(&mut self, path: &str, value: &str) -> Result<(), Error> {
+        let (tag, sub_tag) = parse_path(path)?;
+        if tag == Tags::Crc.code() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tag 63 is computed when the payload is written",
+            ));
+        }
+
+        let value = match sub_tag {
+            None => {
+                validate_tag(tag, value)?;
+                if is_template(tag) {
+                    split_tlv(value)?;
+                }
+                value.to_string()
+            }
+            Some(sub_tag) => {
+                validate_sub_tag(tag, sub_tag, value)?;
+                let mut sub_fields = self.sub_fields(tag)?;
+                upsert(&mut sub_fields, sub_tag, value.to_string());
+                let joined = join(&sub_fields);
+                validate_tag(tag, &joined)?;
+                joined
+            }
+        };
+
+        upsert(&mut self.fields, tag, value);
+        Ok(())
+    }
+
+    /// Removes the value at `path` and returns it. Removing the last sub-tag of a template
+    /// removes the template as well.
+    pub fn remove(&mut self, path: &str) -> Option<String> {
+        let (tag, sub_tag) = parse_path(path).ok()?;
+        let index = self.fields.binary_search_by_key(&tag, |(t, _)| *t).ok()?;
+
+        let Some(sub_tag) = sub_tag else {
+            return Some(self.fields.remove(index).1);
+        };
+
+        let mut sub_fields = self.sub_fields(tag).ok()?;
+        let sub_index = sub_fields
+            .binary_search_by_key(&sub_tag, |(t, _)| *t)
+            .ok()?;
+        let (_, removed) = sub_fields.remove(sub_index);
+
+        if sub_fields.is_empty() {
+            self.fields.remove(index);
+        } else {
+            self.fields[index].1 = join(&sub_fields);
+        }
+        Some(removed)
+    }
+
+    /// Iterates over the top-level tags in the order they are written.
+    pub fn iter(&self) -> impl Iterator<Item = (u8, &str)> {
+        self.fields
+            .iter()
+            .map(|(tag, value)| (*tag, value.as_str()))
+    }
+
+    fn field(&self, tag: u8) -> Option<&str> {
+        self.fields
+            .binary_search_by_key(&tag, |(t, _)| *t)
+            .ok()
+            .map(|index| self.fields[index].1.as_str())
+    }
+
+    fn sub_fields(&self, tag: u8) -> Result<Vec<(u8, String)>, Error> {
+        if !is_template(tag) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Tag {:02} is not a template", tag),
+            ));
+        }
+
+        let mut sub_fields = Vec::new();
+        if let Some(value) = self.field(tag) {
+            for (sub_tag, sub_value) in split_tlv(value)? {
+                upsert(&mut sub_fields, sub_tag, sub_value.to_string());
+            }
+        }
+        Ok(sub_fields)
+    }
+}
+
+/// Re-orders `qr` canonically and recomputes tag 63, so equal content always hashes the same.
+pub fn normalize(qr: &str) -> Result<String, Error> {
+    Ok(Payload::parse(qr)?.to_string())
+}
+
+impl Display for Payload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut writer = CrcWriter::new(f);
+        for (tag, value) in &self.fields {
+            write_tlv!(writer, (tag, value))?;
+        }
+        write!(writer, "{:02}{:02}", Tags::Crc.code(), 4)?;
+        let crc = writer.crc();
+        write!(writer.into_inner(), "{:04X}", crc)
+    }
+}
+
+impl FromStr for Payload {
+    type Err = Error;
+
+    fn from_str(qr: &str) -> Result<Self, Self::Err> {
+        Self::parse(qr)
+    }
+}
+
+fn parse_path(path: &str) -> Result<(u8, Option<u8>), Error> {
+    fn code(part: &str, path: &str) -> Result<u8, Error> {
+        if part.len() != 2 || !part.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Invalid tag path '{}'", path),
+            ));
+        }
+        part.parse().map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("Invalid tag path '{}'", path),
+            )
+        })
+    }
+
+    match path.split_once('.') {
+        None => Ok((code(path, path)?, None)),
+        Some((tag, sub_tag)) => Ok((code(tag, path)?, Some(code(sub_tag, path)?))),
+    }
+}
+
+fn validate_tag(tag: u8, value: &str) -> Result<(), Error> {
+    check_value(value)?;
+    match Tags::from_code(tag) {
+        Some(known) => known
+            .validate_length(value)
+            .map_err(|e| Error::new(ErrorKind::InvalidInput, e)),
+        None => Ok(()),
+    }
+}
+
+fn validate_sub_tag(tag: u8, sub_tag: u8, value: &str) -> Result<(), Error> {
+    check_value(value)?;
+    match Tags::from_code(tag).and_then(|tag| SubTags::from_code(tag, sub_tag)) {
+        Some(known) => known
+            .validate_length(value)
+            .map_err(|e| Error::new(ErrorKind::InvalidInput, e)),
+        None => Ok(()),
+    }
+}
+
+fn check_value(value: &str) -> Result<(), Error> {
+    if value.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Value must not be empty; use remove instead",
+        ));
+    }
+    if value.len() > MAX_VALUE_LENGTH {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("Value exceeds max length {}", MAX_VALUE_LENGTH),
+        ));
+    }
+    Ok(())
+}
+
+fn upsert(fields: &mut Vec<(u8, String)>, tag: u8, value: String) {
+    match fields.binary_search_by_key(&tag, |(t, _)| *t) {
+        Ok(index) => fields[index].1 = value,
+        Err(index) => fields.insert(index, (tag, value)),
+    }
+}
+
+fn join(fields: &[(u8, String)]) -> String {
+    let mut joined = String::new();
+    for (tag, value) in fields {
+        // Writing into a `String` cannot fail.
+        let _ = write_tlv!(joined, (tag, value));
+    }
+    joined
+}
@@ -0,0 +1,352 @@
+use crate::extension::Extensions;
+use crate::hash;
+use crate::models::{
+    AdditionalDataField, AdditionalDataTemplate, Bakong, CardNetwork, CountryCode, Crc16Ccitt,
+    IndivualInformation, MerchantAccountTemplate, MerchantCity, MerchantInformation,
+    MerchantInformationLanguageTemplate, MerchantLocation, MerchantType, NetworkMerchantAccount,
+    PointOfInitialMethod, TipOrConvenienceFee, TipOrConvenienceIndicator, TransactionAmount,
+    TransactionCurrency, UnionPayMerchant, UnreservedField, UnreservedTemplate,
+};
+use crate::validation::{self, Finding, Severity};
+use chrono::{DateTime, Utc};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+fn value_error(e: impl ToString) -> PyErr {
+    PyValueError::new_err(e.to_string())
+}
+
+/// Lets `Bakong(unionpay_merchant=...)` take either a `UnionPayMerchant` or its raw string.
+#[derive(FromPyObject)]
+enum UnionPayMerchantArg {
+    Typed(UnionPayMerchant),
+    Raw(String),
+}
+
+#[pymethods]
+impl Bakong {
+    #[new]
+    #[pyo3(signature = (
+        qr_type,
+        merchant_type,
+        merchant_name,
+        merchant_category_code = None,
+        merchant_city = None,
+        country_code = None,
+        additional_data_template = None,
+        unionpay_merchant = None,
+        merchant_information_language_template = None,
+        network_merchant_accounts = Vec::new(),
+        merchant_account_templates = Vec::new(),
+        tip_or_convenience_fee = None,
+        postal_code = None,
+        unreserved_templates = Vec::new(),
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn py_new(
+        qr_type: PointOfInitialMethod,
+        merchant_type: MerchantType,
+        merchant_name: String,
+        merchant_category_code: Option<String>,
+        merchant_city: Option<MerchantCity>,
+        country_code: Option<CountryCode>,
+        additional_data_template: Option<AdditionalDataTemplate>,
+        unionpay_merchant: Option<UnionPayMerchantArg>,
+        merchant_information_language_template: Option<MerchantInformationLanguageTemplate>,
+        network_merchant_accounts: Vec<NetworkMerchantAccount>,
+        merchant_account_templates: Vec<MerchantAccountTemplate>,
+        tip_or_convenience_fee: Option<TipOrConvenienceFee>,
+        postal_code: Option<String>,
+        unreserved_templates: Vec<UnreservedTemplate>,
+    ) -> Self {
+        Self {
+            qr_type,
+            merchant_type,
+            merchant_category_code,
+            tip_or_convenience_fee,
+            merchant_name,
+            merchant_city,
+            postal_code,
+            country_code,
+            additional_data_template,
+            unionpay_merchant: unionpay_merchant.map(|arg| match arg {
+                UnionPayMerchantArg::Typed(merchant) => merchant,
+                UnionPayMerchantArg::Raw(value) => UnionPayMerchant::from_string(&value),
+            }),
+            network_merchant_accounts,
+            merchant_account_templates,
+            merchant_information_language_template,
+            unreserved_templates,
+            extensions: Extensions::default(),
+        }
+    }
+
+    #[staticmethod]
+    #[pyo3(name = "decode")]
+    fn py_decode(qr: &str) -> PyResult<Self> {
+        Bakong::decode_qr(qr).map_err(value_error)
+    }
+
+    #[pyo3(name = "generate")]
+    fn py_generate(&self) -> PyResult<String> {
+        self.generate_qr().map_err(value_error)
+    }
+
+    #[pyo3(name = "location")]
+    fn py_location(&self) -> Option<MerchantLocation> {
+        self.location()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[pymethods]
+impl IndivualInformation {
+    #[new]
+    #[pyo3(signature = (bakong_account_identifier, individual_account_information = None, acquiring_bank = None))]
+    fn py_new(
+        bakong_account_identifier: String,
+        individual_account_information: Option<String>,
+        acquiring_bank: Option<String>,
+    ) -> Self {
+        Self {
+            bakong_account_identifier,
+            individual_account_information,
+            acquiring_bank,
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[pymethods]
+impl MerchantInformation {
+    #[new]
+    fn py_new(
+        bakong_account_identifier: String,
+        merchant_identifier: String,
+        acquiring_bank: String,
+    ) -> Self {
+        Self {
+            bakong_account_identifier,
+            merchant_identifier,
+            acquiring_bank,
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[pymethods]
+impl AdditionalDataTemplate {
+    #[new]
+    #[pyo3(signature = (
+        bill_number = None,
+        store_label = None,
+        terminal_label = None,
+        mobile_number = None,
+        purpose_of_transaction = None,
+    ))]
+    fn py_new(
+        bill_number: Option<String>,
+        store_label: Option<String>,
+        terminal_label: Option<String>,
+        mobile_number: Option<String>,
+        purpose_of_transaction: Option<String>,
+    ) -> Self {
+        Self {
+            bill_number,
+            store_label,
+            terminal_label,
+            mobile_number,
+            purpose_of_transaction,
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[pymethods]
+impl AdditionalDataField {
+    #[new]
+    #[pyo3(signature = (expiration_timestamp, creation_timestamp = None))]
+    fn py_new(
+        expiration_timestamp: DateTime<Utc>,
+        creation_timestamp: Option<DateTime<Utc>>,
+    ) -> PyResult<Self> {
+        AdditionalDataField::with_creation(
+            creation_timestamp.unwrap_or_else(Utc::now),
+            expiration_timestamp,
+        )
+        .map_err(value_error)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[pymethods]
+impl MerchantInformationLanguageTemplate {
+    #[new]
+    fn py_new(
+        language_preference: String,
+        merchant_name_alternate_language: String,
+        merchant_city_alternate_language: String,
+    ) -> Self {
+        Self {
+            language_preference,
+            merchant_name_alternate_language,
+            merchant_city_alternate_language,
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[pymethods]
+impl MerchantLocation {
+    #[new]
+    #[pyo3(signature = (province, district = None, commune = None))]
+    fn py_new(
+        province: MerchantCity,
+        district: Option<String>,
+        commune: Option<String>,
+    ) -> PyResult<Self> {
+        MerchantLocation::new(province, district, commune).map_err(value_error)
+    }
+
+    #[staticmethod]
+    #[pyo3(name = "from_postal_code")]
+    fn py_from_postal_code(postal_code: &str) -> PyResult<Self> {
+        MerchantLocation::from_postal_code(postal_code).map_err(value_error)
+    }
+
+    #[pyo3(name = "iso_code")]
+    fn py_iso_code(&self) -> String {
+        self.iso_code()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[pymethods]
+impl MerchantAccountTemplate {
+    #[new]
+    fn py_new(tag: u8, value: String) -> PyResult<Self> {
+        MerchantAccountTemplate::new(tag, value).map_err(value_error)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[pymethods]
+impl UnreservedField {
+    #[new]
+    fn py_new(tag: u8, value: String) -> Self {
+        Self { tag, value }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[pymethods]
+impl UnreservedTemplate {
+    #[new]
+    #[pyo3(signature = (tag, guid, fields = Vec::new()))]
+    fn py_new(tag: u8, guid: String, fields: Vec<UnreservedField>) -> PyResult<Self> {
+        UnreservedTemplate::new(tag, guid, fields).map_err(value_error)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[pymethods]
+impl Finding {
+    fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[pyfunction]
+fn decode(qr: &str) -> PyResult<Bakong> {
+    Bakong::decode_qr(qr).map_err(value_error)
+}
+
+#[pyfunction]
+fn generate(bakong: &Bakong) -> PyResult<String> {
+    bakong.generate_qr().map_err(value_error)
+}
+
+#[pyfunction]
+fn validate(qr: &str) -> Vec<Finding> {
+    validation::validate(qr)
+}
+
+#[pyfunction]
+fn verify_crc(qr: &str) -> bool {
+    Crc16Ccitt::verify(qr)
+}
+
+#[pyfunction]
+fn md5_hash(qr: &str) -> String {
+    hash::md5_hash(qr)
+}
+
+#[pyfunction]
+fn short_hash(qr: &str) -> String {
+    hash::short_hash(qr)
+}
+
+#[pymodule]
+fn khqr_sdk(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<Bakong>()?;
+    module.add_class::<MerchantType>()?;
+    module.add_class::<IndivualInformation>()?;
+    module.add_class::<MerchantInformation>()?;
+    module.add_class::<MerchantCity>()?;
+    module.add_class::<MerchantLocation>()?;
+    module.add_class::<CountryCode>()?;
+    module.add_class::<PointOfInitialMethod>()?;
+    module.add_class::<TransactionCurrency>()?;
+    module.add_class::<TransactionAmount>()?;
+    module.add_class::<AdditionalDataTemplate>()?;
+    module.add_class::<AdditionalDataField>()?;
+    module.add_class::<MerchantInformationLanguageTemplate>()?;
+    module.add_class::<NetworkMerchantAccount>()?;
+    module.add_class::<MerchantAccountTemplate>()?;
+    module.add_class::<CardNetwork>()?;
+    module.add_class::<UnionPayMerchant>()?;
+    module.add_class::<TipOrConvenienceFee>()?;
+    module.add_class::<TipOrConvenienceIndicator>()?;
+    module.add_class::<UnreservedTemplate>()?;
+    module.add_class::<UnreservedField>()?;
+    module.add_class::<Extensions>()?;
+    module.add_class::<Finding>()?;
+    module.add_class::<Severity>()?;
+    module.add_function(wrap_pyfunction!(decode, module)?)?;
+    module.add_function(wrap_pyfunction!(generate, module)?)?;
+    module.add_function(wrap_pyfunction!(validate, module)?)?;
+    module.add_function(wrap_pyfunction!(verify_crc, module)?)?;
+    module.add_function(wrap_pyfunction!(md5_hash, module)?)?;
+    module.add_function(wrap_pyfunction!(short_hash, module)?)?;
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use crate::error::{Error, ErrorKind};
+use qrcode::QrCode;
+use qrcode::render::svg;
+
+/// Largest `size` the renderers accept; the image is allocated up front, so the caller-supplied
+/// size must be bounded.
+pub const MAX_IMAGE_SIZE: u32 = 2048;
+
+fn check_size(size: u32) -> Result<(), Error> {
+    if size > MAX_IMAGE_SIZE {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "Image size must be at most {} pixels, got {}",
+                MAX_IMAGE_SIZE, size
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Renders `qr` as an SVG document at least `size` pixels wide, up to `MAX_IMAGE_SIZE`.
+pub fn render_svg(qr: &str, size: u32) -> Result<String, Error> {
+    check_size(size)?;
+    let code = QrCode::new(qr.as_bytes()).map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+    Ok(code
+        .render::<svg::Color>()
+        .min_dimensions(size, size)
+        .quiet_zone(true)
+        .build())
+}
+
+/// Renders `qr` as PNG bytes at least `size` pixels wide, up to `MAX_IMAGE_SIZE`.
+#[cfg(feature = "png")]
+pub fn render_png(qr: &str, size: u32) -> Result<Vec<u8>, Error> {
+    check_size(size)?;
+    let code = QrCode::new(qr.as_bytes()).map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+    let image = code
+        .render::<image::Luma<u8>>()
+        .min_dimensions(size, size)
+        .quiet_zone(true)
+        .build();
+
+    let mut png = Vec::new();
+    image
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .map_err(Error::other)?;
+    Ok(png)
+}
@@ -0,0 +1,236 @@
+use crate::error::Error;
+use crate::models::{
+    Bakong, CountryCode, Crc16Ccitt, DUITNOW_GUID, DuitNow, IDR_CURRENCY_CODE, MYR_CURRENCY_CODE,
+    PROMPTPAY_BILL_PAYMENT_AID, PROMPTPAY_CREDIT_TRANSFER_AID, PromptPay, QRIS_GUID, Qris,
+    THB_CURRENCY_CODE, Tags, VIETQR_GUID, VND_CURRENCY_CODE, VietQr,
+};
+use crate::tlv::split_tlv;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use chrono::{DateTime, Utc};
+
+/// A decoded payload tagged with the scheme it belongs to, for routing.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(clippy::large_enum_variant)]
+pub enum DecodedQr {
+    Bakong(Bakong),
+    PromptPay(PromptPay),
+    VietQr(VietQr),
+    DuitNow(DuitNow),
+    Qris(Qris),
+}
+
+impl DecodedQr {
+    pub fn generate_qr(&self) -> Result<String, Error> {
+        match self {
+            Self::Bakong(bakong) => bakong.generate_qr(),
+            Self::PromptPay(promptpay) => promptpay.generate_qr(),
+            Self::VietQr(vietqr) => vietqr.generate_qr(),
+            Self::DuitNow(duitnow) => duitnow.generate_qr(),
+            Self::Qris(qris) => qris.generate_qr(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn decode_any(qr: &str) -> Result<DecodedQr, Error> {
+    decode_any_at(qr, Utc::now())
+}
+
+/// Decodes `qr` with the decoder of the scheme `detect_scheme` finds, falling back to Bakong.
+/// `now` is used for the Bakong tag 99 expiry check.
+pub fn decode_any_at(qr: &str, now: DateTime<Utc>) -> Result<DecodedQr, Error> {
+    match detect_scheme(qr).scheme {
+        Scheme::PromptPay => PromptPay::decode_qr(qr).map(DecodedQr::PromptPay),
+        Scheme::VietQr => VietQr::decode_qr(qr).map(DecodedQr::VietQr),
+        Scheme::DuitNow => DuitNow::decode_qr(qr).map(DecodedQr::DuitNow),
+        Scheme::Qris => Qris::decode_qr(qr).map(DecodedQr::Qris),
+        _ => Bakong::decode_qr_at(qr, now).map(DecodedQr::Bakong),
+    }
+}
+
+/// The scheme a merchant-presented payload belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Scheme {
+    BakongIndividual,
+    BakongMerchant,
+    PromptPay,
+    VietQr,
+    DuitNow,
+    Qris,
+    /// Only a UnionPay merchant account (tags 15–16) identifies the payload.
+    UnionPay,
+    /// Only a Visa merchant account (tags 02–03) identifies the payload.
+    Visa,
+    /// Only a Mastercard merchant account (tags 04–05) identifies the payload.
+    Mastercard,
+    /// A TLV payload none of the above matched.
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Confidence {
+    Low,
+    Medium,
+    High,
+}
+
+/// The outcome of `detect_scheme`, with the signals that led to it.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SchemeDetection {
+    pub scheme: Scheme,
+    pub confidence: Confidence,
+    pub reasons: Vec<String>,
+}
+
+struct Candidate {
+    scheme: Scheme,
+    country: CountryCode,
+    currencies: &'static [&'static str],
+}
+
+/// Classifies `qr` from the GUIDs of its account templates (tags 26–51), cross-checked against
+/// tag 58 and tag 53. A scheme identified by its template is `High` when country and currency
+/// agree and `Medium` otherwise; a payload identified only by a card network tag is `Medium`,
+/// and anything else is `Unknown` with `Low` confidence. A CRC mismatch caps the result at
+/// `Medium`.
+pub fn detect_scheme(qr: &str) -> SchemeDetection {
+    let mut reasons = Vec::new();
+    let fields = match split_tlv(qr) {
+        Ok(fields) => fields,
+        Err(e) => {
+            reasons.push(format!("not a TLV payload: {}", e));
+            return SchemeDetection {
+                scheme: Scheme::Unknown,
+                confidence: Confidence::Low,
+                reasons,
+            };
+        }
+    };
+    let top = |tag: u8| fields.iter().find(|(t, _)| *t == tag).map(|(_, v)| *v);
+    let guid = |tag: u8| {
+        split_tlv(top(tag)?)
+            .ok()?
+            .into_iter()
+            .find(|(sub_tag, _)| *sub_tag == 0)
+            .map(|(_, value)| value)
+    };
+
+    let mut identified = None;
+    for tag in [29, 30] {
+        if let Some(aid @ (PROMPTPAY_CREDIT_TRANSFER_AID | PROMPTPAY_BILL_PAYMENT_AID)) = guid(tag)
+        {
+            reasons.push(format!("tag {}.00 is the PromptPay AID {}", tag, aid));
+            identified.get_or_insert(Candidate {
+                scheme: Scheme::PromptPay,
+                country: CountryCode::TH,
+                currencies: &[THB_CURRENCY_CODE],
+            });
+        }
+    }
+    if guid(38) == Some(VIETQR_GUID) {
+        reasons.push(format!("tag 38.00 is the NAPAS GUID {}", VIETQR_GUID));
+        identified.get_or_insert(Candidate {
+            scheme: Scheme::VietQr,
+            country: CountryCode::VN,
+            currencies: &[VND_CURRENCY_CODE],
+        });
+    }
+    if guid(26) == Some(DUITNOW_GUID) {
+        reasons.push(format!("tag 26.00 is the DuitNow GUID {}", DUITNOW_GUID));
+        identified.get_or_insert(Candidate {
+            scheme: Scheme::DuitNow,
+            country: CountryCode::MY,
+            currencies: &[MYR_CURRENCY_CODE],
+        });
+    }
+    if guid(51) == Some(QRIS_GUID) {
+        reasons.push(format!("tag 51.00 is the QRIS GUID {}", QRIS_GUID));
+        identified.get_or_insert(Candidate {
+            scheme: Scheme::Qris,
+            country: CountryCode::ID,
+            currencies: &[IDR_CURRENCY_CODE],
+        });
+    }
+    for (tag, scheme) in [(29, Scheme::BakongIndividual), (30, Scheme::BakongMerchant)] {
+        if let Some(account) = guid(tag).filter(|account| account.contains('@')) {
+            reasons.push(format!("tag {}.00 is the Bakong account {}", tag, account));
+            identified.get_or_insert(Candidate {
+                scheme,
+                country: CountryCode::KH,
+                currencies: &["116", "840"],
+            });
+        }
+    }
+
+    let networks = [
+        (15, Scheme::UnionPay, "UnionPay"),
+        (16, Scheme::UnionPay, "UnionPay"),
+        (2, Scheme::Visa, "Visa"),
+        (3, Scheme::Visa, "Visa"),
+        (4, Scheme::Mastercard, "Mastercard"),
+        (5, Scheme::Mastercard, "Mastercard"),
+    ];
+    let mut network = None;
+    for (tag, scheme, name) in networks {
+        if top(tag).is_some() {
+            reasons.push(format!("tag {:02} is a {} merchant account", tag, name));
+            network.get_or_insert(scheme);
+        }
+    }
+
+    let country = top(Tags::CountryCode.code());
+    let currency = top(Tags::TransactionCurrency.code());
+    let (scheme, mut confidence) = match (identified, network) {
+        (Some(candidate), _) => {
+            let mut agrees = true;
+            if country != Some(candidate.country.code()) {
+                agrees = false;
+                reasons.push(format!(
+                    "tag 58 is {}, expected {}",
+                    country.unwrap_or("missing"),
+                    candidate.country.code()
+                ));
+            }
+            if !currency.is_some_and(|currency| candidate.currencies.contains(&currency)) {
+                agrees = false;
+                reasons.push(format!(
+                    "tag 53 is {}, expected {}",
+                    currency.unwrap_or("missing"),
+                    candidate.currencies.join(" or ")
+                ));
+            }
+            let confidence = if agrees {
+                Confidence::High
+            } else {
+                Confidence::Medium
+            };
+            (candidate.scheme, confidence)
+        }
+        (None, Some(scheme)) => (scheme, Confidence::Medium),
+        (None, None) => {
+            reasons.push("no known account template or card network tag".to_string());
+            (Scheme::Unknown, Confidence::Low)
+        }
+    };
+
+    if top(Tags::PayloadFormatIndicator.code()) != Some("01") {
+        reasons.push("tag 00 is not 01".to_string());
+        confidence = Confidence::Low;
+    }
+    if top(Tags::Crc.code()).is_some() && !Crc16Ccitt::verify(qr) {
+        reasons.push("CRC does not match the payload".to_string());
+        confidence = confidence.min(Confidence::Medium);
+    }
+
+    SchemeDetection {
+        scheme,
+        confidence,
+        reasons,
+    }
+}
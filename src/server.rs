@@ -0,0 +1,227 @@
+use crate::config::MerchantConfig;
+use crate::error::{Error, ErrorKind};
+use crate::hash::md5_hash;
+use crate::models::{
+    AdditionalDataField, AdditionalDataTemplate, Bakong, PointOfInitialMethod, TransactionAmount,
+    TransactionCurrency,
+};
+use crate::render::{render_png, render_svg};
+use crate::validation::{Finding, Severity, validate};
+use axum::extract::State;
+use axum::extract::rejection::JsonRejection;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+const DEFAULT_IMAGE_SIZE: u32 = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QrImage {
+    Svg,
+    Png,
+}
+
+/// Body of `POST /qr`. Without an `amount` the payload is static in `currency` (KHR by default).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct QrRequest {
+    pub store_id: String,
+    pub terminal: Option<String>,
+    pub amount: Option<TransactionAmount>,
+    pub currency: Option<TransactionCurrency>,
+    pub expires_in_seconds: Option<u32>,
+    pub bill_number: Option<String>,
+    pub image: Option<QrImage>,
+    pub size: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QrResponse {
+    pub payload: String,
+    pub md5: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub svg: Option<String>,
+    /// Base64-encoded PNG.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub png: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct QrPayload {
+    pub qr: String,
+}
+
+/// `valid` is false only when a finding is an error; warnings alone leave a payload valid.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidateResponse {
+    pub valid: bool,
+    pub findings: Vec<Finding>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthResponse {
+    pub status: String,
+    pub version: String,
+}
+
+/// Builds the HTTP service around `config`; serve it with `axum::serve` or drive it in-process.
+pub fn router(config: MerchantConfig) -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .route("/qr", post(generate))
+        .route("/decode", post(decode))
+        .route("/validate", post(validate_qr))
+        .with_state(Arc::new(config))
+}
+
+async fn health() -> Json<HealthResponse> {
+    Json(HealthResponse {
+        status: "ok".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+    })
+}
+
+async fn generate(
+    State(config): State<Arc<MerchantConfig>>,
+    request: Result<Json<QrRequest>, JsonRejection>,
+) -> Result<Json<QrResponse>, ApiError> {
+    let Json(request) = request?;
+
+    let profile = config.store(&request.store_id).ok_or_else(|| {
+        Error::new(
+            ErrorKind::NotFound,
+            format!("Unknown store '{}'", request.store_id),
+        )
+    })?;
+
+    let mut bakong = profile.to_bakong(request.terminal.as_deref(), qr_type(&request)?)?;
+    if let Some(bill_number) = request.bill_number {
+        bakong
+            .additional_data_template
+            .get_or_insert(AdditionalDataTemplate {
+                bill_number: None,
+                store_label: None,
+                terminal_label: None,
+                mobile_number: None,
+                purpose_of_transaction: None,
+            })
+            .bill_number = Some(bill_number);
+    }
+
+    let payload = bakong.generate_qr()?;
+    let size = request.size.unwrap_or(DEFAULT_IMAGE_SIZE);
+    let (svg, png) = match request.image {
+        Some(QrImage::Svg) => (Some(render_svg(&payload, size)?), None),
+        Some(QrImage::Png) => (None, Some(STANDARD.encode(render_png(&payload, size)?))),
+        None => (None, None),
+    };
+
+    Ok(Json(QrResponse {
+        md5: md5_hash(&payload),
+        payload,
+        svg,
+        png,
+    }))
+}
+
+fn qr_type(request: &QrRequest) -> Result<PointOfInitialMethod, Error> {
+    let Some(amount) = &request.amount else {
+        if request.expires_in_seconds.is_some() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "expires_in_seconds requires an amount",
+            ));
+        }
+        return Ok(PointOfInitialMethod::Static(
+            request.currency.clone().unwrap_or(TransactionCurrency::KHR),
+        ));
+    };
+
+    if request
+        .currency
+        .as_ref()
+        .is_some_and(|currency| *currency != amount.currency())
+    {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "currency does not match the amount",
+        ));
+    }
+
+    let additional_data_field = request
+        .expires_in_seconds
+        .map(|seconds| AdditionalDataField::new(Utc::now() + Duration::seconds(seconds.into())))
+        .transpose()?;
+
+    Ok(PointOfInitialMethod::Dynamic {
+        additional_data_field,
+        amount: amount.clone(),
+    })
+}
+
+async fn decode(request: Result<Json<QrPayload>, JsonRejection>) -> Result<Json<Bakong>, ApiError> {
+    let Json(request) = request?;
+    Ok(Json(Bakong::decode_qr(&request.qr)?))
+}
+
+async fn validate_qr(
+    request: Result<Json<QrPayload>, JsonRejection>,
+) -> Result<Json<ValidateResponse>, ApiError> {
+    let Json(request) = request?;
+    let findings = validate(&request.qr);
+    Ok(Json(ValidateResponse {
+        valid: findings
+            .iter()
+            .all(|finding| finding.severity != Severity::Error),
+        findings,
+    }))
+}
+
+struct ApiError {
+    status: StatusCode,
+    kind: ErrorKind,
+    message: String,
+}
+
+impl From<Error> for ApiError {
+    fn from(e: Error) -> Self {
+        let status = match e.kind() {
+            ErrorKind::InvalidInput => StatusCode::BAD_REQUEST,
+            ErrorKind::InvalidData => StatusCode::UNPROCESSABLE_ENTITY,
+            ErrorKind::NotFound => StatusCode::NOT_FOUND,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        Self {
+            status,
+            kind: e.kind(),
+            message: e.to_string(),
+        }
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        Self {
+            status: rejection.status(),
+            kind: ErrorKind::InvalidInput,
+            message: rejection.body_text(),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = serde_json::json!({
+            "error": { "kind": format!("{:?}", self.kind), "message": self.message }
+        });
+        (self.status, Json(body)).into_response()
+    }
+}
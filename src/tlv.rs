@@ -1,4 +1,267 @@
-use std::io::Error;
+use crate::error::{Error, ErrorKind};
+use crate::models::Crc16Ccitt;
+use crate::models::{PointOfInitialMethod, Tags};
+use crate::write_tlv;
+use alloc::format;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
 pub trait TLV {
-    fn to_tlv(&self, temp: &mut String, buffer: &mut String) -> Result<(), Error>;
+    fn tag(&self) -> Tags;
+
+    fn write_value<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error>;
+
+    fn to_tlv<W: Write + ?Sized>(&self, buffer: &mut W) -> Result<(), Error> {
+        let tag = self.tag();
+        let mut counter = LengthCounter::default();
+        self.write_value(&mut counter)?;
+
+        if counter.len() > tag.max_length() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("{:?} exceeds max length {}", tag, tag.max_length()),
+            ));
+        }
+
+        write!(buffer, "{:02}{:02}", tag.code(), counter.len()).map_err(Error::other)?;
+        self.write_value(buffer)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct LengthCounter {
+    len: usize,
+}
+
+impl LengthCounter {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl Write for LengthCounter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.len += s.len();
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct SliceWriter<'a> {
+    buffer: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> SliceWriter<'a> {
+    pub fn new(buffer: &'a mut [u8]) -> Self {
+        Self { buffer, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn as_str(&self) -> &str {
+        // Only whole `&str` values are ever copied in, so the prefix is always valid UTF-8.
+        core::str::from_utf8(&self.buffer[..self.len]).unwrap_or_default()
+    }
+
+    pub fn into_str(self) -> &'a str {
+        let buffer: &'a [u8] = self.buffer;
+        core::str::from_utf8(&buffer[..self.len]).unwrap_or_default()
+    }
+}
+
+impl Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > self.buffer.len() {
+            return Err(fmt::Error);
+        }
+        self.buffer[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct CrcWriter<W> {
+    inner: W,
+    crc: Crc16Ccitt,
+}
+
+impl<W: Write> CrcWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            crc: Crc16Ccitt::new(),
+        }
+    }
+
+    pub fn crc(&self) -> u16 {
+        self.crc.finish()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> CrcWriter<W> {
+    /// Ends the payload with tag 63, whose value is the CRC of everything before it including
+    /// the `6304` header.
+    pub fn finish(mut self) -> Result<(), Error> {
+        write!(&mut self, "{:02}{:02}", Tags::Crc.code(), 4).map_err(Error::other)?;
+        let crc = self.crc();
+        write!(self.inner, "{:04X}", crc).map_err(Error::other)
+    }
+}
+
+impl<W: Write> Write for CrcWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_str(s)?;
+        self.crc.update(s.as_bytes());
+        Ok(())
+    }
+}
+
+/// Splits a complete payload into its top-level fields after the checks every scheme shares:
+/// tag 00 = `01` first, a matching tag 63 and no repeated tags.
+pub(crate) fn split_payload(qr: &str) -> Result<Vec<(u8, &str)>, Error> {
+    if !qr.starts_with("000201") {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "Payload must start with the payload format indicator (tag 00)",
+        ));
+    }
+    if !Crc16Ccitt::verify(qr) {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "CRC is missing or does not match the payload",
+        ));
+    }
+
+    let fields = split_tlv(qr)?;
+    let mut seen = SeenTags::default();
+    for (tag, _) in &fields {
+        if !seen.insert(*tag) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("Duplicate tag '{}'", tag),
+            ));
+        }
+    }
+    Ok(fields)
+}
+
+/// Writes tags 00 and 01 of a scheme payload: static (`11`) or dynamic (`12`).
+pub(crate) fn write_payload_header<W: Write + ?Sized>(
+    writer: &mut W,
+    is_static: bool,
+) -> Result<(), Error> {
+    let method = if is_static { "11" } else { "12" };
+    write_tlv!(writer, (Tags::PayloadFormatIndicator.code(), "01")).map_err(Error::other)?;
+    write_tlv!(writer, (Tags::PointOfInitialMethod.code(), method)).map_err(Error::other)
+}
+
+/// Reads tag 01 of a scheme payload, returning whether it is dynamic. Tag 01 must be `11` or
+/// `12`, and a static (`11`) payload must not carry an amount; without tag 01 a payload is
+/// dynamic exactly when it has one.
+pub(crate) fn decode_dynamic(
+    point_of_initiation: Option<&str>,
+    has_amount: bool,
+) -> Result<bool, Error> {
+    let Some(value) = point_of_initiation else {
+        return Ok(has_amount);
+    };
+    match PointOfInitialMethod::is_static_from_string(value) {
+        Some(true) if has_amount => Err(Error::new(
+            ErrorKind::InvalidData,
+            "Static QR must not carry a transaction amount (tag 54)",
+        )),
+        Some(is_static) => Ok(!is_static),
+        None => Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("Invalid point of initiation method '{}' (tag 01)", value),
+        )),
+    }
+}
+
+/// Remembers which two-digit tags have already been parsed.
+#[derive(Debug, Default)]
+pub(crate) struct SeenTags(u128);
+
+impl SeenTags {
+    /// Records `tag`, returning `false` if it was seen before.
+    pub(crate) fn insert(&mut self, tag: u8) -> bool {
+        let bit = 1u128 << (tag % 128);
+        let fresh = self.0 & bit == 0;
+        self.0 |= bit;
+        fresh
+    }
+}
+
+/// Canonical ordering policy: tags and sub-tags ascend numerically, except that the CRC
+/// (tag 63) always comes last.
+pub(crate) fn canonical_rank(tag: u8) -> u16 {
+    if tag == Tags::Crc.code() {
+        u16::MAX
+    } else {
+        u16::from(tag)
+    }
+}
+
+/// Merchant account templates (26–51), tag 62, tag 64 and the unreserved templates (80–99).
+pub(crate) fn is_template(tag: u8) -> bool {
+    matches!(tag, 26..=51 | 62 | 64 | 80..=99)
+}
+
+/// Splits `value` into its `(tag, value)` pairs without interpreting them.
+pub(crate) fn split_tlv(value: &str) -> Result<Vec<(u8, &str)>, Error> {
+    let mut fields = Vec::new();
+    let mut slice = value;
+
+    while !slice.is_empty() {
+        let (Some(raw_tag), Some(raw_length)) = (slice.get(0..2), slice.get(2..4)) else {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("Truncated TLV '{}'", slice),
+            ));
+        };
+
+        let tag: u8 = raw_tag.parse().map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Invalid tag code '{}'", raw_tag),
+            )
+        })?;
+
+        let length: usize = raw_length.parse().map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Invalid length '{}'", raw_length),
+            )
+        })?;
+
+        let end = 4 + length;
+        let value = slice.get(4..end).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Declared length of tag {:02} exceeds remaining slice", tag),
+            )
+        })?;
+
+        fields.push((tag, value));
+        slice = &slice[end..];
+    }
+
+    Ok(fields)
 }
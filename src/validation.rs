@@ -0,0 +1,135 @@
+use crate::extension::ExtensionRegistry;
+use crate::models::{Bakong, CountryCode, Crc16Ccitt, Tags};
+use crate::tlv::{canonical_rank, is_template, split_tlv};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "python", pyo3::pyclass(eq, eq_int, from_py_object))]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "python", pyo3::pyclass(get_all, eq, from_py_object))]
+pub struct Finding {
+    pub severity: Severity,
+    pub path: Option<String>,
+    pub message: String,
+}
+
+impl Finding {
+    pub fn error(path: Option<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            path,
+            message: message.into(),
+        }
+    }
+
+    pub fn warning(path: Option<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            path,
+            message: message.into(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn validate(qr: &str) -> Vec<Finding> {
+    validate_at(qr, Utc::now())
+}
+
+/// Collects every problem found in `qr`, treating `now` as the current time.
+pub fn validate_at(qr: &str, now: DateTime<Utc>) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    if !Crc16Ccitt::verify(qr) {
+        findings.push(Finding::error(
+            Some(format!("{:02}", Tags::Crc.code())),
+            "CRC is missing or does not match the payload",
+        ));
+    }
+
+    findings.extend(ordering_findings(qr));
+
+    // The CRC is reported above, so decode the fields without checking it again.
+    match Bakong::decode_fields(qr, now, &ExtensionRegistry::new()) {
+        Ok(bakong) => findings.extend(location_findings(&bakong)),
+        Err(e) => findings.push(Finding::error(None, e.to_string())),
+    }
+
+    findings
+}
+
+/// Warns when a Cambodian postal code (tag 61) lies in another province than the city (tag 60).
+fn location_findings(bakong: &Bakong) -> Option<Finding> {
+    let city = bakong.merchant_city.as_ref()?;
+    let postal_code = bakong.postal_code.as_ref()?;
+    if bakong.country_code != Some(CountryCode::KH)
+        || postal_code.get(..2) == Some(format!("{:02}", city.province_number()).as_str())
+    {
+        return None;
+    }
+    Some(Finding::warning(
+        Some(format!("{:02}", Tags::PostalCode.code())),
+        format!(
+            "Postal code {} is not in {} ({})",
+            postal_code,
+            city.city(),
+            city.iso_code()
+        ),
+    ))
+}
+
+/// Warns about every tag or sub-tag that breaks the canonical order (ascending, tag 63 last).
+fn ordering_findings(qr: &str) -> Vec<Finding> {
+    fn check(fields: &[(u8, &str)], parent: Option<u8>, findings: &mut Vec<Finding>) {
+        let path = |tag: u8| match parent {
+            Some(parent) => format!("{:02}.{:02}", parent, tag),
+            None => format!("{:02}", tag),
+        };
+        // Tag 63 only means CRC at the top level.
+        let rank = |tag: u8| match parent {
+            Some(_) => u16::from(tag),
+            None => canonical_rank(tag),
+        };
+
+        for pair in fields.windows(2) {
+            let (previous, current) = (pair[0].0, pair[1].0);
+            if rank(current) < rank(previous) {
+                findings.push(Finding::warning(
+                    Some(path(current)),
+                    format!(
+                        "Tag {} is out of canonical order (after {})",
+                        path(current),
+                        path(previous)
+                    ),
+                ));
+            }
+        }
+    }
+
+    let mut findings = Vec::new();
+    let Ok(fields) = split_tlv(qr) else {
+        return findings;
+    };
+
+    check(&fields, None, &mut findings);
+    for (tag, value) in &fields {
+        if is_template(*tag)
+            && let Ok(sub_fields) = split_tlv(value)
+        {
+            check(&sub_fields, Some(*tag), &mut findings);
+        }
+    }
+    findings
+}
@@ -0,0 +1,91 @@
+use crate::models::Bakong;
+use crate::render;
+use crate::validation;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub fn generate(bakong: Bakong) -> Result<String, JsError> {
+    bakong
+        .generate_qr()
+        .map_err(|e| JsError::new(&e.to_string()))
+}
+
+#[wasm_bindgen]
+pub fn decode(qr: &str) -> Result<Bakong, JsError> {
+    Bakong::decode_qr(qr).map_err(|e| JsError::new(&e.to_string()))
+}
+
+#[wasm_bindgen(unchecked_return_type = "Finding[]")]
+pub fn validate(qr: &str) -> Result<JsValue, JsError> {
+    serde_wasm_bindgen::to_value(&validation::validate(qr)).map_err(JsError::from)
+}
+
+#[wasm_bindgen(js_name = renderSvg)]
+pub fn render_svg(qr: &str, size: u32) -> Result<String, JsError> {
+    render::render_svg(qr, size).map_err(|e| JsError::new(&e.to_string()))
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use crate::models::{PointOfInitialMethod, TransactionAmount, TransactionCurrency};
+    use crate::validation::{Finding, Severity};
+    use alloc::string::String;
+    use alloc::vec::Vec;
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    const STATIC_KHR: &str = "00020101021129110007abc@dev53031165802KH5902Ex6010Phnom Penh63042C8E";
+
+    fn error_message(error: JsError) -> String {
+        JsValue::from(error)
+            .dyn_into::<js_sys::Error>()
+            .map(|error| error.message().into())
+            .unwrap_or_default()
+    }
+
+    fn findings(qr: &str) -> Vec<Finding> {
+        serde_wasm_bindgen::from_value(validate(qr).unwrap()).unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    fn generate_and_decode_round_trip_through_js() {
+        let decoded = decode(STATIC_KHR).unwrap();
+        assert_eq!(
+            decoded.qr_type,
+            PointOfInitialMethod::Static(TransactionCurrency::KHR)
+        );
+
+        // Cross the JS boundary the way a caller's object does.
+        let js = serde_wasm_bindgen::to_value(&decoded).unwrap();
+        let bakong: Bakong = serde_wasm_bindgen::from_value(js).unwrap();
+        assert_eq!(generate(bakong).unwrap(), STATIC_KHR);
+    }
+
+    #[wasm_bindgen_test]
+    fn generate_reports_invalid_input_as_an_error() {
+        let mut bakong = decode(STATIC_KHR).unwrap();
+        bakong.qr_type = PointOfInitialMethod::Dynamic {
+            additional_data_field: None,
+            amount: TransactionAmount::KHR(1),
+        };
+        let message = error_message(generate(bakong).unwrap_err());
+        assert_eq!(message, "KHR amount must be at least 100, got 1");
+    }
+
+    #[wasm_bindgen_test]
+    fn decode_reports_errors_as_js_errors() {
+        let message = error_message(decode("000201").unwrap_err());
+        assert!(!message.is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn validate_returns_findings() {
+        assert!(findings(STATIC_KHR).is_empty());
+
+        let broken = STATIC_KHR.replace("2C8E", "0000");
+        let findings = findings(&broken);
+        assert_eq!(findings[0].severity, Severity::Error);
+        assert_eq!(findings[0].path.as_deref(), Some("63"));
+    }
+}
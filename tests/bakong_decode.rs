@@ -0,0 +1,224 @@
+mod common;
+
+use common::with_crc;
+use khqr_sdk::{
+    Bakong, CountryCode, MerchantAccountTemplate, MerchantType, PointOfInitialMethod,
+    TransactionAmount, TransactionCurrency,
+};
+
+const STATIC_KHR: &str = "00020101021129110007abc@dev53031165802KH5902Ex6010Phnom Penh";
+
+#[test]
+fn decodes_every_bakong_account_sub_tag() {
+    let merchant =
+        with_crc("00020101021130260007abc@dev0103M010204ACLB53031165802KH5902Ex6010Phnom Penh");
+    let bakong = Bakong::decode_qr(&merchant).unwrap();
+    let MerchantType::Merchant(information) = &bakong.merchant_type else {
+        panic!(
+            "expected a merchant account, got {:?}",
+            bakong.merchant_type
+        );
+    };
+    assert_eq!(information.bakong_account_identifier, "abc@dev");
+    assert_eq!(information.merchant_identifier, "M01");
+    assert_eq!(information.acquiring_bank, "ACLB");
+    assert_eq!(bakong.generate_qr().unwrap(), merchant);
+
+    let individual =
+        with_crc("00020101021129190007abc@dev0204ACLB53031165802KH5902Ex6010Phnom Penh");
+    let bakong = Bakong::decode_qr(&individual).unwrap();
+    let MerchantType::Indivual(information) = &bakong.merchant_type else {
+        panic!(
+            "expected an individual account, got {:?}",
+            bakong.merchant_type
+        );
+    };
+    assert_eq!(information.bakong_account_identifier, "abc@dev");
+    assert_eq!(information.acquiring_bank.as_deref(), Some("ACLB"));
+    assert_eq!(bakong.generate_qr().unwrap(), individual);
+}
+
+#[test]
+fn rejects_a_wrong_crc() {
+    let qr = with_crc(STATIC_KHR);
+    assert!(Bakong::decode_qr(&qr).is_ok());
+
+    let wrong = format!("{}63040000", STATIC_KHR);
+    let err = Bakong::decode_qr(&wrong).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "CRC is missing or does not match the payload"
+    );
+    assert!(Bakong::decode_qr(STATIC_KHR).is_err());
+}
+
+#[test]
+fn derives_a_static_qr_without_tag_01_from_tag_53() {
+    let qr = with_crc("00020129110007abc@dev53038405802KH5902Ex6010Phnom Penh");
+    let bakong = Bakong::decode_qr(&qr).unwrap();
+    assert_eq!(
+        bakong.qr_type,
+        PointOfInitialMethod::Static(TransactionCurrency::USD)
+    );
+}
+
+#[test]
+fn derives_a_dynamic_qr_without_tag_01_from_tag_54() {
+    let qr = with_crc("00020129110007abc@dev5303840540412.55802KH5902Ex6010Phnom Penh");
+    let bakong = Bakong::decode_qr(&qr).unwrap();
+    assert_eq!(
+        bakong.qr_type,
+        PointOfInitialMethod::Dynamic {
+            additional_data_field: None,
+            amount: TransactionAmount::USD(12.5),
+        }
+    );
+}
+
+#[test]
+fn rejects_multi_byte_characters_without_panicking() {
+    for qr in [
+        "000201ក",
+        "0002010102122930ក0012abc@dev5802KH",
+        "00020101021129110007ក@de53031165802KH5902Ex6010Phnom Penh",
+        "0002010102116208ក01X",
+    ] {
+        assert!(Bakong::decode_qr(&with_crc(qr)).is_err(), "{}", qr);
+    }
+}
+
+#[test]
+fn rejects_currencies_other_than_khr_and_usd() {
+    let qr = with_crc("00020101021129110007abc@dev53037645802KH5902Ex6010Phnom Penh");
+    let err = Bakong::decode_qr(&qr).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Bakong QR currency must be KHR or USD, got THB"
+    );
+}
+
+#[test]
+fn keeps_other_merchant_account_templates() {
+    let qr = with_crc("00020101021126080004ABCD29110007abc@dev53031165802KH5902Ex6010Phnom Penh");
+    let bakong = Bakong::decode_qr(&qr).unwrap();
+    assert_eq!(
+        bakong.merchant_account_templates,
+        vec![MerchantAccountTemplate::new(26, "0004ABCD".to_string()).unwrap()]
+    );
+    assert_eq!(bakong.generate_qr().unwrap(), qr);
+}
+
+#[test]
+fn writes_merchant_account_templates_in_tag_order() {
+    let mut bakong = Bakong::decode_qr(&with_crc(STATIC_KHR)).unwrap();
+    bakong.merchant_account_templates = vec![
+        MerchantAccountTemplate::new(51, "0002ID".to_string()).unwrap(),
+        MerchantAccountTemplate::new(26, "0004ABCD".to_string()).unwrap(),
+    ];
+    assert_eq!(
+        bakong.generate_qr().unwrap(),
+        with_crc(
+            "00020101021126080004ABCD29110007abc@dev51060002ID53031165802KH5902Ex6010Phnom Penh"
+        )
+    );
+
+    bakong
+        .merchant_account_templates
+        .push(bakong.merchant_account_templates[0].clone());
+    let err = bakong.generate_qr().unwrap_err();
+    assert_eq!(err.to_string(), "Duplicate merchant account template 51");
+
+    let err = MerchantAccountTemplate::new(29, "0004ABCD".to_string()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Tag 29 is not a merchant account template (26-28, 31-51)"
+    );
+}
+
+#[test]
+fn rejects_duplicate_tags_and_sub_tags() {
+    for (body, message) in [
+        (format!("{}5902Ey", STATIC_KHR), "Duplicate tag '59'"),
+        (
+            "00020101021129220007abc@dev0007abc@dev53031165802KH5902Ex6010Phnom Penh".to_string(),
+            "Duplicate IndivualInformation sub-tag 0",
+        ),
+        (
+            format!("{}62100101A0101B", STATIC_KHR),
+            "Duplicate AdditionalDataTemplate sub-tag 1",
+        ),
+    ] {
+        let err = Bakong::decode_qr(&with_crc(&body)).unwrap_err();
+        assert_eq!(err.to_string(), message, "{}", body);
+    }
+}
+
+#[test]
+fn rejects_an_unknown_point_of_initiation_method() {
+    for method in ["13", "1x"] {
+        let qr = with_crc(&STATIC_KHR.replacen("010211", &format!("0102{}", method), 1));
+        let err = Bakong::decode_qr(&qr).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("Invalid point of initiation method '{}' (tag 01)", method)
+        );
+    }
+}
+
+#[test]
+fn rejects_an_amount_on_a_static_qr() {
+    let qr = with_crc(
+        "00020101021129110007abc@dev530311654041000\
+                       5802KH5902Ex6010Phnom Penh",
+    );
+    let err = Bakong::decode_qr(&qr).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Static QR must not carry a transaction amount (tag 54)"
+    );
+}
+
+#[test]
+fn rejects_a_missing_or_unsupported_payload_format_indicator() {
+    let qr = with_crc(&STATIC_KHR.replacen("000201", "000202", 1));
+    let err = Bakong::decode_qr(&qr).unwrap_err();
+    assert_eq!(err.to_string(), "Unsupported payload format indicator '02'");
+
+    let err = Bakong::decode_qr(&with_crc(&STATIC_KHR["000201".len()..])).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Payload must start with the payload format indicator (tag 00)"
+    );
+}
+
+#[test]
+fn keeps_every_supported_country_code() {
+    for code in [
+        "KH", "TH", "VN", "LA", "MY", "SG", "ID", "PH", "MM", "BN", "CN", "HK", "JP", "KR",
+    ] {
+        let qr = with_crc(&STATIC_KHR.replacen("5802KH", &format!("5802{}", code), 1));
+        let bakong = Bakong::decode_qr(&qr).unwrap();
+        assert_eq!(
+            bakong.country_code,
+            CountryCode::from_code(code),
+            "{}",
+            code
+        );
+        assert_eq!(bakong.generate_qr().unwrap(), qr);
+    }
+}
+
+#[test]
+fn rejects_unsupported_and_missing_country_codes() {
+    for code in ["US", "kh", "K1"] {
+        let qr = with_crc(&STATIC_KHR.replacen("5802KH", &format!("5802{}", code), 1));
+        let err = Bakong::decode_qr(&qr).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("Unsupported country code '{}'", code)
+        );
+    }
+
+    let err = Bakong::decode_qr(&with_crc(&STATIC_KHR.replacen("5802KH", "", 1))).unwrap_err();
+    assert_eq!(err.to_string(), "Missing country code (tag 58)");
+}
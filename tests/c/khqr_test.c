@@ -0,0 +1,123 @@
+// Exercises the C ABI end to end; built and run by tests/capi.rs.
+#include <stdio.h>
+#include <string.h>
+
+#include "khqr.h"
+
+static int failures = 0;
+
+#define CHECK(condition)                                                      \
+    do {                                                                      \
+        if (!(condition)) {                                                   \
+            fprintf(stderr, "%s:%d: check failed: %s (last error: %s)\n",    \
+                    __FILE__, __LINE__, #condition,                           \
+                    khqr_last_error() ? khqr_last_error() : "none");          \
+            failures++;                                                       \
+        }                                                                     \
+    } while (0)
+
+static const char *field(const KhqrPayload *payload, int which) {
+    const char *value = NULL;
+    CHECK(khqr_payload_field(payload, which, &value) == KHQR_STATUS_OK);
+    return value;
+}
+
+static void test_round_trip(void) {
+    KhqrRequest request;
+    memset(&request, 0, sizeof request);
+    request.bakong_account_identifier = "abc@dev";
+    request.merchant_identifier = "123456";
+    request.acquiring_bank = "Dev Bank";
+    request.merchant_name = "Example";
+    request.merchant_city = "Phnom Penh";
+    request.currency = KHQR_CURRENCY_USD;
+    request.amount = "12.5";
+    request.bill_number = "INV-1";
+
+    char *qr = NULL;
+    CHECK(khqr_generate(&request, &qr) == KHQR_STATUS_OK);
+    if (qr == NULL) {
+        return;
+    }
+    CHECK(khqr_validate(qr) == KHQR_STATUS_OK);
+
+    KhqrPayload *payload = NULL;
+    CHECK(khqr_decode(qr, &payload) == KHQR_STATUS_OK);
+    if (payload != NULL) {
+        CHECK(strcmp(field(payload, KHQR_FIELD_BAKONG_ACCOUNT_IDENTIFIER), "abc@dev") == 0);
+        CHECK(strcmp(field(payload, KHQR_FIELD_MERCHANT_IDENTIFIER), "123456") == 0);
+        CHECK(strcmp(field(payload, KHQR_FIELD_MERCHANT_NAME), "Example") == 0);
+        CHECK(strcmp(field(payload, KHQR_FIELD_AMOUNT), "12.50") == 0);
+        CHECK(strcmp(field(payload, KHQR_FIELD_BILL_NUMBER), "INV-1") == 0);
+        CHECK(strcmp(field(payload, KHQR_FIELD_COUNTRY_CODE), "KH") == 0);
+        CHECK(field(payload, KHQR_FIELD_STORE_LABEL) == NULL);
+        CHECK(!khqr_payload_is_static(payload));
+        CHECK(khqr_payload_currency(payload) == KHQR_CURRENCY_USD);
+        CHECK(khqr_payload_expiration_ms(payload) == 0);
+
+        const char *value = "untouched";
+        CHECK(khqr_payload_field(payload, KHQR_FIELD_COUNTRY_CODE + 1, &value) ==
+              KHQR_STATUS_INVALID_INPUT);
+        CHECK(khqr_payload_field(payload, -1, &value) == KHQR_STATUS_INVALID_INPUT);
+        CHECK(strcmp(value, "untouched") == 0);
+        CHECK(khqr_payload_field(payload, KHQR_FIELD_AMOUNT, NULL) ==
+              KHQR_STATUS_NULL_POINTER);
+        CHECK(khqr_payload_field(NULL, KHQR_FIELD_AMOUNT, &value) ==
+              KHQR_STATUS_NULL_POINTER);
+        khqr_free(payload);
+    }
+    khqr_string_free(qr);
+}
+
+static void test_rejected_input(void) {
+    KhqrPayload *payload = NULL;
+    CHECK(khqr_decode("000201\xe1\x9e\x80" "0000", &payload) == KHQR_STATUS_INVALID_INPUT);
+    CHECK(payload == NULL);
+    CHECK(khqr_validate("000201\xe1\x9e\x80") == KHQR_STATUS_INVALID_INPUT);
+    CHECK(khqr_decode("00020101021129110007abc@dev53031165802KH5902Ex6010Phnom Penh63040000",
+                      &payload) == KHQR_STATUS_INVALID_DATA);
+    CHECK(khqr_decode(NULL, &payload) == KHQR_STATUS_NULL_POINTER);
+    CHECK(khqr_last_error() != NULL);
+
+    KhqrRequest request;
+    memset(&request, 0, sizeof request);
+    request.merchant_name = "Example";
+    char *qr = NULL;
+    CHECK(khqr_generate(&request, &qr) == KHQR_STATUS_NULL_POINTER);
+    CHECK(qr == NULL);
+
+    /* The currency is read as an integer, so an undeclared value is an error, not UB. */
+    request.bakong_account_identifier = "abc@dev";
+    request.currency = 764;
+    CHECK(khqr_generate(&request, &qr) == KHQR_STATUS_INVALID_INPUT);
+    CHECK(qr == NULL);
+    CHECK(strcmp(khqr_last_error(), "Unknown currency 764") == 0);
+    request.currency = KHQR_CURRENCY_KHR;
+    CHECK(khqr_generate(&request, &qr) == KHQR_STATUS_OK);
+    khqr_string_free(qr);
+}
+
+static void test_validate_severity(void) {
+    /* Tag 15 after tag 53 is legacy ordering: a warning, not an error. */
+    CHECK(khqr_validate("00020101021229110007abc@dev530311615041234540412345802KH5902Ex"
+                        "6010Phnom Penh6304114C") == KHQR_STATUS_OK);
+    CHECK(khqr_validate("00020101021129110007abc@dev53031165802KH5902Ex6010Phnom Penh63040000") ==
+          KHQR_STATUS_INVALID_DATA);
+}
+
+static void test_crc(void) {
+    uint16_t crc = 0;
+    CHECK(khqr_crc("123456789", &crc) == KHQR_STATUS_OK);
+    CHECK(crc == 0x29B1);
+}
+
+int main(void) {
+    test_round_trip();
+    test_rejected_input();
+    test_validate_severity();
+    test_crc();
+    if (failures == 0) {
+        printf("all C API checks passed\n");
+    }
+    return failures == 0 ? 0 : 1;
+}
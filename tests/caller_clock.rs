@@ -0,0 +1,70 @@
+//! The clock-dependent functions take the current time from the caller, so `no_std` targets can
+//! check the tag 99 expiry without `Utc::now()`.
+
+mod common;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use common::with_crc;
+use khqr_sdk::{AdditionalDataField, Bakong, PointOfInitialMethod, TransactionAmount};
+
+fn created() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2020, 1, 1, 8, 0, 0).unwrap()
+}
+
+/// A dynamic QR created at `created()` that expires ten minutes later.
+fn dynamic_qr() -> (Bakong, String) {
+    let mut bakong = Bakong::decode_qr(&with_crc(
+        "00020101021129110007abc@dev53031165802KH5902Ex6010Phnom Penh",
+    ))
+    .unwrap();
+    let field =
+        AdditionalDataField::with_creation(created(), created() + Duration::minutes(10)).unwrap();
+    bakong.qr_type = PointOfInitialMethod::Dynamic {
+        additional_data_field: Some(field),
+        amount: TransactionAmount::KHR(1000),
+    };
+    let qr = bakong.generate_qr().unwrap();
+    (bakong, qr)
+}
+
+#[test]
+fn decodes_against_the_caller_supplied_time() {
+    let (bakong, qr) = dynamic_qr();
+
+    let decoded = Bakong::decode_qr_at(&qr, created() + Duration::minutes(5)).unwrap();
+    assert_eq!(decoded, bakong);
+
+    let err = Bakong::decode_qr_at(&qr, created() + Duration::minutes(11)).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Expiration timestamp is already in the past"
+    );
+    // The std clock is years past the expiry.
+    assert!(Bakong::decode_qr(&qr).is_err());
+}
+
+#[test]
+fn parses_tag_99_against_the_caller_supplied_time() {
+    let field =
+        AdditionalDataField::with_creation(created(), created() + Duration::minutes(10)).unwrap();
+    let value = format!(
+        "0013{}0113{}",
+        created().timestamp_millis(),
+        (created() + Duration::minutes(10)).timestamp_millis()
+    );
+    assert_eq!(
+        AdditionalDataField::from_string_at(&value, created()).unwrap(),
+        field
+    );
+    assert!(AdditionalDataField::from_string_at(&value, created() + Duration::hours(1)).is_err());
+}
+
+#[test]
+fn rejects_an_expiry_before_the_creation_time() {
+    let err = AdditionalDataField::with_creation(created(), created() - Duration::seconds(1))
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Expiration timestamp is before creation timestamp"
+    );
+}
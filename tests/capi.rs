@@ -0,0 +1,62 @@
+#![cfg(all(feature = "capi", unix))]
+
+use std::path::Path;
+use std::process::Command;
+
+/// Builds the crate as a static library, checks that `include/khqr.h` matches the freshly
+/// generated header, compiles `tests/c/khqr_test.c` against them and runs the program.
+#[test]
+fn c_program_passes() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let work_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("capi");
+    let target_dir = work_dir.join("target");
+    let header_dir = work_dir.join("include");
+    std::fs::create_dir_all(&header_dir).unwrap();
+
+    // A separate target directory keeps this build off the lock `cargo test` holds.
+    let status = Command::new(env!("CARGO"))
+        .current_dir(manifest_dir)
+        .args([
+            "rustc",
+            "--lib",
+            "--crate-type",
+            "staticlib",
+            "--features",
+            "capi",
+        ])
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .env("KHQR_HEADER_DIR", &header_dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "building the static library failed");
+
+    let generated = std::fs::read_to_string(header_dir.join("khqr.h")).unwrap();
+    let checked_in = std::fs::read_to_string(manifest_dir.join("include/khqr.h")).unwrap();
+    assert_eq!(
+        checked_in, generated,
+        "include/khqr.h is out of date; rebuild with KHQR_HEADER_DIR=include"
+    );
+
+    let program = work_dir.join("khqr_test");
+    let status = Command::new(std::env::var("CC").unwrap_or_else(|_| "cc".to_string()))
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-I")
+        .arg(&header_dir)
+        .arg(manifest_dir.join("tests/c/khqr_test.c"))
+        .arg(target_dir.join("debug/libkhqr_sdk.a"))
+        .args(["-lpthread", "-ldl", "-lm", "-o"])
+        .arg(&program)
+        .status()
+        .unwrap();
+    assert!(status.success(), "compiling tests/c/khqr_test.c failed");
+
+    let output = Command::new(&program).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
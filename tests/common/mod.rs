@@ -0,0 +1,7 @@
+use khqr_sdk::crc16_ccitt;
+
+/// Appends tag 63 with the CRC of `body`.
+pub fn with_crc(body: &str) -> String {
+    let body = format!("{}6304", body);
+    format!("{}{:04X}", body, crc16_ccitt(&body))
+}
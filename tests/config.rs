@@ -0,0 +1,170 @@
+#![cfg(feature = "config")]
+
+use khqr_sdk::{MerchantConfig, MerchantType};
+use std::io::ErrorKind;
+
+const TOML: &str = r#"
+[[stores]]
+id = "bkk-1"
+merchant_name = "Example"
+merchant_city = "Phnom Penh"
+postal_code = "120101"
+terminals = ["T-01", "T-02"]
+
+[stores.account]
+type = "merchant"
+bakong_account_identifier = "abc@dev"
+merchant_identifier = "123456789"
+acquiring_bank = "Dev Bank"
+
+[[stores]]
+id = "bkk"
+merchant_name = "Other"
+
+[stores.account]
+type = "individual"
+bakong_account_identifier = "other@dev"
+"#;
+
+fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn loads_toml() {
+    let config = MerchantConfig::from_toml_str(TOML).unwrap();
+    let store = config.store("bkk-1").unwrap();
+    assert_eq!(store.merchant_name, "Example");
+    assert_eq!(store.postal_code.as_deref(), Some("120101"));
+    assert_eq!(store.terminals, ["T-01", "T-02"]);
+    assert!(matches!(store.merchant_type, MerchantType::Merchant(_)));
+    assert!(matches!(
+        config.store("bkk").unwrap().merchant_type,
+        MerchantType::Indivual(_)
+    ));
+}
+
+#[test]
+fn loads_json() {
+    let config = MerchantConfig::from_json_str(
+        r#"{"stores": [{
+            "id": "bkk-1",
+            "merchant_name": "Example",
+            "account": {"type": "individual", "bakong_account_identifier": "abc@dev"},
+            "language": {"preference": "km", "merchant_name": "Ex", "merchant_city": "PP"}
+        }]}"#,
+    )
+    .unwrap();
+    let language = config.stores[0]
+        .merchant_information_language_template
+        .as_ref()
+        .unwrap();
+    assert_eq!(language.language_preference, "km");
+}
+
+#[test]
+fn toml_errors_point_at_line_and_column() {
+    let source = TOML.replace(
+        "merchant_city = \"Phnom Penh\"",
+        "merchant_city = \"Atlantis\"",
+    );
+    let err = MerchantConfig::from_toml_str(&source).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert!(
+        err.to_string()
+            .starts_with("<toml>:5:17: Unknown merchant city 'Atlantis'"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn json_errors_point_at_line_and_column() {
+    let err = MerchantConfig::from_json_str(
+        "{\"stores\": [{\n  \"id\": \"a\",\n  \"merchant_name\": 5\n}]}",
+    )
+    .unwrap_err();
+    assert!(err.to_string().starts_with("<json>:3:20: "), "{}", err);
+}
+
+#[test]
+fn rejects_duplicate_store_ids() {
+    let source = TOML.replace("id = \"bkk\"\n", "id = \"bkk-1\"\n");
+    let err = MerchantConfig::from_toml_str(&source).unwrap_err();
+    assert_eq!(err.to_string(), "<toml>: duplicate store id 'bkk-1'");
+}
+
+#[test]
+fn env_overrides_pick_the_longest_store_key() {
+    let mut config = MerchantConfig::from_toml_str(TOML).unwrap();
+    config
+        .apply_env_overrides(vars(&[
+            ("KHQR_BKK_1_MERCHANT_NAME", "Renamed"),
+            ("KHQR_BKK_MERCHANT_NAME", "Short"),
+            ("KHQR_BKK_1_TERMINALS", "A, B"),
+            ("KHQR_CONFIG", "merchants.toml"),
+            ("PATH", "/usr/bin"),
+        ]))
+        .unwrap();
+    assert_eq!(config.store("bkk-1").unwrap().merchant_name, "Renamed");
+    assert_eq!(config.store("bkk-1").unwrap().terminals, ["A", "B"]);
+    assert_eq!(config.store("bkk").unwrap().merchant_name, "Short");
+}
+
+#[test]
+fn env_overrides_reject_unknown_fields() {
+    let mut config = MerchantConfig::from_toml_str(TOML).unwrap();
+    let err = config
+        .apply_env_overrides(vars(&[("KHQR_BKK_MERCHANT_NAM", "Typo")]))
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "KHQR_BKK_MERCHANT_NAM: unknown field 'MERCHANT_NAM'"
+    );
+}
+
+#[test]
+fn env_overrides_validate_values() {
+    let mut config = MerchantConfig::from_toml_str(TOML).unwrap();
+    let err = config
+        .apply_env_overrides(vars(&[("KHQR_BKK_BAKONG_ACCOUNT_IDENTIFIER", "no-at")]))
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "KHQR_BKK_BAKONG_ACCOUNT_IDENTIFIER: Bakong account identifier must contain exactly one '@'"
+    );
+
+    let err = config
+        .apply_env_overrides(vars(&[("KHQR_BKK_MERCHANT_IDENTIFIER", "1")]))
+        .unwrap_err();
+    assert!(err.to_string().contains("not used by individual accounts"));
+}
+
+#[test]
+fn env_overrides_set_the_language_template() {
+    let mut config = MerchantConfig::from_toml_str(TOML).unwrap();
+    config
+        .apply_env_overrides(vars(&[
+            ("KHQR_BKK_LANGUAGE_PREFERENCE", "km"),
+            ("KHQR_BKK_LANGUAGE_MERCHANT_NAME", "Example"),
+            ("KHQR_BKK_LANGUAGE_MERCHANT_CITY", "Phnom Penh"),
+        ]))
+        .unwrap();
+    let language = config
+        .store("bkk")
+        .unwrap()
+        .merchant_information_language_template
+        .as_ref()
+        .unwrap();
+    assert_eq!(language.language_preference, "km");
+    assert_eq!(language.merchant_city_alternate_language, "Phnom Penh");
+
+    let mut config = MerchantConfig::from_toml_str(TOML).unwrap();
+    let err = config
+        .apply_env_overrides(vars(&[("KHQR_BKK_LANGUAGE_PREFERENCE", "km")]))
+        .unwrap_err();
+    assert!(err.to_string().contains("must be overridden together"));
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use common::with_crc;
+use khqr_sdk::{Change, diff};
+use std::process::Command;
+
+const STATIC_KHR: &str = "00020101021129110007abc@dev53031165802KH5902Ex6010Phnom Penh";
+const DYNAMIC_USD: &str =
+    "00020101021229110007abc@dev5303840540410005802KH5902Ex6010Phnom Penh62080704T-12";
+
+#[test]
+fn reports_changes_by_path_with_notes() {
+    let result = diff(&with_crc(STATIC_KHR), &with_crc(DYNAMIC_USD), false).unwrap();
+    assert_eq!(
+        result.changes,
+        vec![
+            Change::Changed {
+                path: "01".to_string(),
+                old: "11".to_string(),
+                new: "12".to_string(),
+            },
+            Change::Changed {
+                path: "53".to_string(),
+                old: "116".to_string(),
+                new: "840".to_string(),
+            },
+            Change::Added {
+                path: "54".to_string(),
+                value: "1000".to_string(),
+            },
+            Change::Added {
+                path: "62.07".to_string(),
+                value: "T-12".to_string(),
+            },
+        ]
+    );
+    assert_eq!(
+        result.notes,
+        vec![
+            "QR changed from static to dynamic",
+            "amount changed from no amount (KHR) to 1000 USD",
+        ]
+    );
+    assert_eq!(
+        result.to_string(),
+        "~ 01: \"11\" -> \"12\"\n\
+         ~ 53: \"116\" -> \"840\"\n\
+         + 54: \"1000\"\n\
+         + 62.07: \"T-12\"\n\
+         note: QR changed from static to dynamic\n\
+         note: amount changed from no amount (KHR) to 1000 USD\n"
+    );
+}
+
+#[test]
+fn compares_tag_63_only_when_asked() {
+    let qr = with_crc(STATIC_KHR);
+    let mut tampered = qr.clone();
+    tampered.replace_range(qr.len() - 4.., "0000");
+
+    let result = diff(&qr, &tampered, false).unwrap();
+    assert!(result.changes.is_empty());
+    assert_eq!(
+        result.notes,
+        vec!["new payload does not decode: CRC is missing or does not match the payload"]
+    );
+
+    let result = diff(&qr, &tampered, true).unwrap();
+    assert_eq!(result.changes.len(), 1);
+    assert_eq!(result.changes[0].path(), "63");
+}
+
+#[test]
+fn reports_repeated_tags_and_sub_tags() {
+    let qr = with_crc(STATIC_KHR);
+    let repeated = with_crc(&format!("{}5902Ey62100101A0101B", STATIC_KHR));
+
+    let result = diff(&qr, &repeated, false).unwrap();
+    assert_eq!(
+        result.notes,
+        vec![
+            "new payload does not decode: Duplicate tag '59'",
+            "new payload repeats 59: \"Ex\", then \"Ey\"",
+            "new payload repeats 62.01: \"A\", then \"B\"",
+        ]
+    );
+    assert_eq!(
+        result.changes,
+        vec![Change::Added {
+            path: "62.01".to_string(),
+            value: "A".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn identical_payloads_have_an_empty_diff() {
+    let qr = with_crc(STATIC_KHR);
+    assert!(diff(&qr, &qr, true).unwrap().is_empty());
+}
+
+#[test]
+fn cli_exits_with_the_diff_status() {
+    let run = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_khqr-diff"))
+            .args(args)
+            .output()
+            .unwrap()
+    };
+
+    let same = with_crc(STATIC_KHR);
+    let output = run(&[&same, &same]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stdout.is_empty());
+
+    let output = run(&[&same, &with_crc(DYNAMIC_USD)]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("~ 01: \"11\" -> \"12\"\n"));
+
+    let output = run(&[&same]);
+    assert_eq!(output.status.code(), Some(2));
+    let output = run(&[&same, "not a payload"]);
+    assert_eq!(output.status.code(), Some(2));
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use common::with_crc;
+use khqr_sdk::{
+    Confidence, DecodedQr, DuitNow, Scheme, TransactionAmount, decode_any, detect_scheme,
+};
+
+/// A static merchant payload laid out like PayNet's samples: the 14-character AID
+/// `A0000006150001` in 26.00, followed by the acquirer (26.01) and account (26.02).
+const MERCHANT_STATIC: &str = "00020101021126480014A00000061500010106890053021600000001234567895204549953034585802MY5907Example6012Kuala Lumpur6304BCBF";
+
+fn duitnow(amount: Option<f32>) -> DuitNow {
+    DuitNow {
+        acquirer_id: "890053".to_string(),
+        account: "0000000123456789".to_string(),
+        merchant_category_code: None,
+        dynamic: amount.is_some(),
+        amount: amount.map(TransactionAmount::MYR),
+        merchant_name: Some("Example".to_string()),
+        merchant_city: Some("Kuala Lumpur".to_string()),
+        additional_data_template: None,
+    }
+}
+
+#[test]
+fn round_trips_static_and_dynamic_payloads() {
+    for duitnow in [duitnow(None), duitnow(Some(12.5))] {
+        let qr = duitnow.generate_qr().unwrap();
+        assert_eq!(DuitNow::decode_qr(&qr).unwrap(), duitnow);
+    }
+}
+
+#[test]
+fn keeps_a_dynamic_payload_without_an_amount_dynamic() {
+    let duitnow = DuitNow {
+        dynamic: true,
+        ..duitnow(None)
+    };
+    let qr = duitnow.generate_qr().unwrap();
+    assert!(qr.starts_with("000201010212"));
+
+    let decoded = DuitNow::decode_qr(&qr).unwrap();
+    assert_eq!(decoded, duitnow);
+    assert_eq!(decoded.generate_qr().unwrap(), qr);
+}
+
+#[test]
+fn decodes_a_paynet_merchant_payload() {
+    let decoded = DuitNow::decode_qr(MERCHANT_STATIC).unwrap();
+    assert_eq!(
+        decoded,
+        DuitNow {
+            merchant_category_code: Some("5499".to_string()),
+            ..duitnow(None)
+        }
+    );
+    assert_eq!(decoded.generate_qr().unwrap(), MERCHANT_STATIC);
+}
+
+#[test]
+fn detects_a_paynet_merchant_payload() {
+    let detection = detect_scheme(MERCHANT_STATIC);
+    assert_eq!(detection.scheme, Scheme::DuitNow);
+    assert_eq!(detection.confidence, Confidence::High);
+    assert!(matches!(
+        decode_any(MERCHANT_STATIC),
+        Ok(DecodedQr::DuitNow(_))
+    ));
+}
+
+#[test]
+fn rejects_an_amount_on_a_static_payload() {
+    let body = MERCHANT_STATIC.replace("5802MY", "54045.005802MY");
+    let qr = with_crc(&body[..body.len() - 8]);
+    assert_eq!(
+        DuitNow::decode_qr(&qr).unwrap_err().to_string(),
+        "Static QR must not carry a transaction amount (tag 54)"
+    );
+}
+
+#[test]
+fn rejects_non_finite_and_non_ringgit_amounts() {
+    for amount in [f32::NAN, f32::INFINITY, 0.0] {
+        let err = duitnow(Some(amount)).generate_qr().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("MYR amount must be at least 0.01, got {}", amount)
+        );
+    }
+
+    let err = DuitNow {
+        amount: Some(TransactionAmount::THB(12.5)),
+        ..duitnow(None)
+    }
+    .generate_qr()
+    .unwrap_err();
+    assert_eq!(err.to_string(), "DuitNow amount must be in MYR, got THB");
+
+    for amount in ["inf", "NaN"] {
+        let body = MERCHANT_STATIC.replacen("010211", "010212", 1);
+        let body = body.replace("5802MY", &format!("54{:02}{}5802MY", amount.len(), amount));
+        let err = DuitNow::decode_qr(&with_crc(&body[..body.len() - 8])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "MYR amount must be at least 0.01, got {}",
+                amount.parse::<f32>().unwrap()
+            )
+        );
+    }
+}
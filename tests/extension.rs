@@ -0,0 +1,215 @@
+mod common;
+
+use common::with_crc;
+use khqr_sdk::{
+    Bakong, Error, ErrorKind, ExtensionRegistry, ExtensionTag, TagExtension, UnreservedField,
+    UnreservedTemplate,
+};
+
+/// A string extension in tag `TAG`, or in sub-tag `SUB` of it when `SUB` is not 0.
+#[derive(Debug, Clone, PartialEq)]
+struct At<const TAG: u8, const SUB: u8>(String);
+
+impl<const TAG: u8, const SUB: u8> TagExtension for At<TAG, SUB> {
+    const TAG: ExtensionTag = if SUB == 0 {
+        ExtensionTag::Tag(TAG)
+    } else {
+        ExtensionTag::SubTag(TAG, SUB)
+    };
+
+    fn parse(value: &str) -> Result<Self, Error> {
+        if value.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidData, "empty"));
+        }
+        Ok(Self(value.to_string()))
+    }
+
+    fn write_value<W: core::fmt::Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        writer.write_str(&self.0).map_err(Error::other)
+    }
+}
+
+impl<const TAG: u8, const SUB: u8> At<TAG, SUB> {
+    fn new(value: &str) -> Self {
+        Self(value.to_string())
+    }
+}
+
+type LoyaltyMember = At<62, 50>;
+type PartnerAccount = At<27, 0>;
+type Campaign = At<80, 5>;
+
+const STATIC_KHR: &str = "00020101021129110007abc@dev53031165802KH5902Ex6010Phnom Penh";
+
+fn registry() -> ExtensionRegistry {
+    let mut registry = ExtensionRegistry::new();
+    registry
+        .register::<LoyaltyMember>()
+        .unwrap()
+        .register::<PartnerAccount>()
+        .unwrap()
+        .register::<Campaign>()
+        .unwrap();
+    registry
+}
+
+#[test]
+fn reports_which_tags_are_left_to_extensions() {
+    for tag in [26, 28, 31, 51, 80, 98] {
+        assert!(ExtensionTag::Tag(tag).is_available(), "{}", tag);
+    }
+    for tag in [0, 15, 29, 30, 52, 62, 63, 79, 99] {
+        assert!(!ExtensionTag::Tag(tag).is_available(), "{}", tag);
+    }
+    for (tag, sub_tag) in [(62, 10), (62, 99), (80, 1), (98, 99), (99, 2), (99, 99)] {
+        assert!(ExtensionTag::SubTag(tag, sub_tag).is_available());
+    }
+    for (tag, sub_tag) in [(62, 9), (80, 0), (99, 0), (99, 1), (29, 5), (64, 10)] {
+        assert!(!ExtensionTag::SubTag(tag, sub_tag).is_available());
+    }
+}
+
+#[test]
+fn round_trips_extensions_through_decode_and_generate() {
+    let registry = registry();
+    let mut bakong = registry.decode_qr(&with_crc(STATIC_KHR)).unwrap();
+    bakong.unreserved_templates.push(
+        UnreservedTemplate::new(
+            80,
+            "COM.EXAMPLE.LOYALTY".to_string(),
+            vec![UnreservedField {
+                tag: 1,
+                value: "A".to_string(),
+            }],
+        )
+        .unwrap(),
+    );
+    bakong
+        .extensions
+        .insert(LoyaltyMember::new("M-1001"))
+        .unwrap();
+    bakong
+        .extensions
+        .insert(PartnerAccount::new("0004ABCD"))
+        .unwrap();
+    bakong.extensions.insert(Campaign::new("C-7")).unwrap();
+
+    let qr = bakong.generate_qr().unwrap();
+    assert_eq!(
+        qr,
+        with_crc(
+            "00020101021127080004ABCD29110007abc@dev53031165802KH5902Ex6010Phnom Penh\
+             62105006M-100180350019COM.EXAMPLE.LOYALTY0101A0503C-7"
+        )
+    );
+
+    let decoded = registry.decode_qr(&qr).unwrap();
+    assert_eq!(
+        decoded.extension::<LoyaltyMember>(),
+        Some(&LoyaltyMember::new("M-1001"))
+    );
+    assert_eq!(
+        decoded.extension::<PartnerAccount>(),
+        Some(&PartnerAccount::new("0004ABCD"))
+    );
+    assert_eq!(decoded.extension::<Campaign>(), Some(&Campaign::new("C-7")));
+    assert_eq!(decoded.generate_qr().unwrap(), qr);
+
+    assert!(Bakong::decode_qr(&qr).is_err());
+}
+
+#[test]
+fn requires_the_template_of_an_unreserved_sub_tag() {
+    let mut bakong = registry().decode_qr(&with_crc(STATIC_KHR)).unwrap();
+    bakong.extensions.insert(Campaign::new("C-7")).unwrap();
+    let err = bakong.generate_qr().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Extension 80.05 requires an unreserved template with tag 80"
+    );
+}
+
+#[test]
+fn merges_extension_sub_tags_into_tag_62() {
+    let qr = with_crc(&format!("{}62150105INV-15002M1", STATIC_KHR));
+    let decoded = registry().decode_qr(&qr).unwrap();
+    assert_eq!(
+        decoded
+            .additional_data_template
+            .as_ref()
+            .and_then(|template| template.bill_number.as_deref()),
+        Some("INV-1")
+    );
+    assert_eq!(
+        decoded.extension::<LoyaltyMember>(),
+        Some(&LoyaltyMember::new("M1"))
+    );
+    assert_eq!(decoded.generate_qr().unwrap(), qr);
+}
+
+#[test]
+fn reports_the_path_of_a_value_that_fails_to_parse() {
+    let qr = with_crc(&format!("{}62045000", STATIC_KHR));
+    let err = registry().decode_qr(&qr).unwrap_err();
+    assert_eq!(err.to_string(), "Extension 62.50: empty");
+}
+
+#[test]
+fn rejects_tags_the_sdk_models() {
+    fn register<T: TagExtension>() -> String {
+        ExtensionRegistry::new()
+            .register::<T>()
+            .map(|_| ())
+            .unwrap_err()
+            .to_string()
+    }
+
+    assert_eq!(
+        register::<At<29, 0>>(),
+        "Tag 29 is modelled by the SDK and cannot be extended"
+    );
+    assert_eq!(
+        register::<At<62, 1>>(),
+        "Tag 62.01 is modelled by the SDK and cannot be extended"
+    );
+    assert_eq!(
+        register::<At<99, 1>>(),
+        "Tag 99.01 is modelled by the SDK and cannot be extended"
+    );
+
+    let mut bakong = Bakong::decode_qr(&with_crc(STATIC_KHR)).unwrap();
+    let err = bakong
+        .extensions
+        .insert(At::<59, 0>::new("Ex"))
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Tag 59 is modelled by the SDK and cannot be extended"
+    );
+}
+
+#[test]
+fn rejects_overlapping_registrations() {
+    let mut registry = registry();
+    for (err, message) in [
+        (
+            registry
+                .register::<LoyaltyMember>()
+                .map(|_| ())
+                .unwrap_err(),
+            "Extension 62.50 overlaps the registered extension 62.50",
+        ),
+        (
+            registry.register::<At<80, 0>>().map(|_| ()).unwrap_err(),
+            "Extension 80 overlaps the registered extension 80.05",
+        ),
+        (
+            registry.register::<At<27, 0>>().map(|_| ()).unwrap_err(),
+            "Extension 27 overlaps the registered extension 27",
+        ),
+    ] {
+        assert_eq!(err.to_string(), message);
+    }
+    assert!(registry.is_registered::<LoyaltyMember>());
+    assert!(!registry.is_registered::<At<80, 0>>());
+}
@@ -0,0 +1,121 @@
+mod common;
+
+use common::with_crc;
+use khqr_sdk::{Bakong, MerchantCity, MerchantLocation};
+
+fn location(
+    province: MerchantCity,
+    district: Option<&str>,
+    commune: Option<&str>,
+) -> MerchantLocation {
+    MerchantLocation {
+        province,
+        district: district.map(str::to_string),
+        commune: commune.map(str::to_string),
+    }
+}
+
+#[test]
+fn maps_every_province_to_its_iso_code() {
+    for number in 1..=25 {
+        let code = format!("KH-{}", number);
+        let province = MerchantCity::from_iso_code(&code).unwrap();
+        assert_eq!(province.province_number(), number);
+        assert_eq!(province.iso_code(), code);
+    }
+    assert_eq!(
+        MerchantCity::from_iso_code("KH-7"),
+        Some(MerchantCity::Kampot)
+    );
+    assert_eq!(MerchantCity::PoiPet.iso_code(), "KH-1");
+    for code in ["KH-0", "KH-26", "TH-10", "KH-x"] {
+        assert_eq!(MerchantCity::from_iso_code(code), None, "{}", code);
+    }
+}
+
+#[test]
+fn reads_the_province_and_district_from_a_postal_code() {
+    assert_eq!(
+        MerchantLocation::from_postal_code("120101").unwrap(),
+        location(MerchantCity::PhnomPenh, Some("Chamkar Mon"), None)
+    );
+    assert_eq!(
+        MerchantLocation::from_postal_code("070101").unwrap(),
+        location(MerchantCity::Kampot, None, None)
+    );
+
+    let err = MerchantLocation::from_postal_code("260101").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Postal code '260101' does not start with a province number"
+    );
+    let err = MerchantLocation::from_postal_code("12010").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Cambodian postal code must be exactly 6 digits"
+    );
+}
+
+#[test]
+fn embeds_districts_for_phnom_penh_only() {
+    let districts = MerchantCity::PhnomPenh.districts().unwrap();
+    assert_eq!(districts.len(), 14);
+    assert!(
+        districts
+            .iter()
+            .all(|district| district.code.starts_with("12"))
+    );
+    assert_eq!(MerchantCity::Kampot.districts(), None);
+}
+
+#[test]
+fn validates_districts_and_communes() {
+    assert!(
+        location(
+            MerchantCity::PhnomPenh,
+            Some("Chamkar Mon"),
+            Some("Tonle Basak")
+        )
+        .validate()
+        .is_ok()
+    );
+    // Without embedded districts any non-empty name is accepted.
+    assert!(
+        location(MerchantCity::Kampot, Some("Kampong Bay"), None)
+            .validate()
+            .is_ok()
+    );
+
+    for (location, message) in [
+        (
+            location(MerchantCity::PhnomPenh, Some("Kampong Bay"), None),
+            "Unknown district 'Kampong Bay' in Phnom Penh",
+        ),
+        (
+            location(MerchantCity::Kampot, Some(""), None),
+            "Unknown district '' in Kampot",
+        ),
+        (
+            location(MerchantCity::Kampot, None, Some("Kampong Bay")),
+            "Commune requires a district",
+        ),
+        (
+            location(MerchantCity::Kampot, Some("Kampong Bay"), Some("")),
+            "Commune must not be empty",
+        ),
+    ] {
+        assert_eq!(location.validate().unwrap_err().to_string(), message);
+    }
+}
+
+#[test]
+fn decodes_a_kampot_merchant_location() {
+    let qr = with_crc("00020101021129110007abc@dev53031165802KH5902Ex6006Kampot6106070101");
+    let bakong = Bakong::decode_qr(&qr).unwrap();
+    assert_eq!(bakong.merchant_city, Some(MerchantCity::Kampot));
+    assert_eq!(
+        bakong.location(),
+        Some(location(MerchantCity::Kampot, None, None))
+    );
+    assert_eq!(bakong.generate_qr().unwrap(), qr);
+}
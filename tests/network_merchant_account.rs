@@ -0,0 +1,88 @@
+mod common;
+
+use common::with_crc;
+use khqr_sdk::{Bakong, CardNetwork, NetworkMerchantAccount, Scheme, detect_scheme};
+
+#[test]
+fn maps_each_tag_to_its_card_network() {
+    for (tags, network) in [
+        (2..=3, Some(CardNetwork::Visa)),
+        (4..=5, Some(CardNetwork::Mastercard)),
+        (6..=8, Some(CardNetwork::Emvco)),
+        (9..=10, Some(CardNetwork::Discover)),
+        (11..=12, Some(CardNetwork::Amex)),
+        (13..=14, Some(CardNetwork::Jcb)),
+        (15..=16, Some(CardNetwork::UnionPay)),
+        (17..=25, Some(CardNetwork::Emvco)),
+        (0..=1, None),
+        (26..=99, None),
+    ] {
+        for tag in tags {
+            assert_eq!(CardNetwork::from_tag(tag), network, "tag {}", tag);
+        }
+    }
+}
+
+#[test]
+fn round_trips_network_accounts_in_tag_order() {
+    let qr = with_crc(
+        "00020101021102164111111111111111041651234567890123461616\
+         620000000000000129110007abc@dev53031165802KH5902Ex6010Phnom Penh",
+    );
+    let bakong = Bakong::decode_qr(&qr).unwrap();
+    assert_eq!(
+        bakong.network_merchant_accounts,
+        vec![
+            NetworkMerchantAccount::new(2, "4111111111111111".to_string()).unwrap(),
+            NetworkMerchantAccount::new(4, "5123456789012346".to_string()).unwrap(),
+            NetworkMerchantAccount::new(16, "6200000000000001".to_string()).unwrap(),
+        ]
+    );
+    assert_eq!(
+        bakong
+            .network_merchant_accounts
+            .iter()
+            .map(NetworkMerchantAccount::network)
+            .collect::<Vec<_>>(),
+        vec![
+            Some(CardNetwork::Visa),
+            Some(CardNetwork::Mastercard),
+            Some(CardNetwork::UnionPay),
+        ]
+    );
+    assert_eq!(bakong.generate_qr().unwrap(), qr);
+
+    let mut reversed = bakong.clone();
+    reversed.network_merchant_accounts.reverse();
+    assert_eq!(reversed.generate_qr().unwrap(), qr);
+}
+
+#[test]
+fn rejects_tags_outside_the_card_network_ranges() {
+    for tag in [1, 15, 26] {
+        let err = NetworkMerchantAccount::new(tag, "123".to_string()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Tag {:02} is not a card network merchant account (02-14, 16-25)",
+                tag
+            )
+        );
+    }
+    let err = NetworkMerchantAccount::new(2, String::new()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Merchant account in tag 02 must not be empty"
+    );
+}
+
+#[test]
+fn detects_a_tag_16_only_payload_as_unionpay() {
+    let qr = with_crc("0002010102111616620000000000000153031565802CN5902Ex6008Shanghai");
+    let detection = detect_scheme(&qr);
+    assert_eq!(detection.scheme, Scheme::UnionPay);
+    assert_eq!(
+        detection.reasons,
+        vec!["tag 16 is a UnionPay merchant account"]
+    );
+}
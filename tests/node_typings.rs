@@ -0,0 +1,76 @@
+use std::collections::BTreeSet;
+use std::fs::OpenOptions;
+use std::path::Path;
+use std::process::Command;
+
+/// Rebuilds the crate with napi's type definitions enabled and checks that `node/index.d.ts`
+/// matches them. Refresh the checked-in file with
+/// `KHQR_TYPINGS_DIR=node cargo test --test node_typings`.
+#[test]
+fn index_d_ts_matches_napi_type_definitions() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let work_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("node");
+    let type_defs = work_dir.join("type_defs.jsonl");
+    std::fs::create_dir_all(&work_dir).unwrap();
+    // napi-derive replaces this file whenever it expands src/node.rs; creating it up front only
+    // keeps the first build quiet.
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&type_defs)
+        .unwrap();
+
+    // A separate target directory keeps this build off the lock `cargo test` holds.
+    let status = Command::new(env!("CARGO"))
+        .current_dir(manifest_dir)
+        .args(["check", "--lib", "--features", "node", "--target-dir"])
+        .arg(work_dir.join("target"))
+        .env("TYPE_DEF_TMP_PATH", &type_defs)
+        .status()
+        .unwrap();
+    assert!(status.success(), "building the node feature failed");
+
+    let generated = render(&std::fs::read_to_string(&type_defs).unwrap());
+    if let Ok(typings_dir) = std::env::var("KHQR_TYPINGS_DIR") {
+        std::fs::write(
+            manifest_dir.join(typings_dir).join("index.d.ts"),
+            &generated,
+        )
+        .unwrap();
+    }
+    let checked_in = std::fs::read_to_string(manifest_dir.join("node/index.d.ts")).unwrap();
+    assert_eq!(
+        checked_in, generated,
+        "node/index.d.ts is out of date; rerun with KHQR_TYPINGS_DIR=node"
+    );
+}
+
+/// Turns napi's one-JSON-object-per-line type definitions into `index.d.ts`, importing the
+/// model types the signatures mention from `types.d.ts`.
+fn render(type_defs: &str) -> String {
+    let mut imports = BTreeSet::new();
+    let mut declarations = String::new();
+    for line in type_defs.lines().filter(|line| !line.trim().is_empty()) {
+        let type_def: serde_json::Value = serde_json::from_str(line).unwrap();
+        let def = type_def["def"].as_str().unwrap();
+        imports.extend(
+            def.split(|c: char| !c.is_ascii_alphanumeric())
+                .filter(|word| word.starts_with(|c: char| c.is_ascii_uppercase()))
+                .map(str::to_string),
+        );
+        declarations.push('\n');
+        declarations.push_str(type_def["js_doc"].as_str().unwrap_or_default());
+        declarations.push_str(def);
+        declarations.push_str(";\n");
+    }
+
+    format!(
+        "// Generated from the #[napi] functions in src/node.rs by tests/node_typings.rs.\n\
+         import type {{ {} }} from \"./types\";\n\
+         \n\
+         export * from \"./types\";\n\
+         {}",
+        imports.into_iter().collect::<Vec<_>>().join(", "),
+        declarations
+    )
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use common::with_crc;
+use khqr_sdk::{ErrorKind, Payload, normalize};
+
+const BODY: &str = "00020101021129110007abc@dev53031165802KH5902Ex6010Phnom Penh";
+
+#[test]
+fn edits_round_trip_through_display() {
+    let mut payload: Payload = with_crc(BODY).parse().unwrap();
+    assert_eq!(payload.get("29.00"), Some("abc@dev"));
+    assert_eq!(payload.get("63"), None);
+
+    payload.set("62.07", "T-12").unwrap();
+    payload.set("62.01", "INV-1").unwrap();
+    payload.set("54", "1000").unwrap();
+    let edited = payload.to_string();
+    assert_eq!(
+        edited,
+        with_crc(
+            "00020101021129110007abc@dev530311654041000\
+             5802KH5902Ex6010Phnom Penh62170105INV-10704T-12"
+        )
+    );
+    assert_eq!(Payload::parse(&edited).unwrap(), payload);
+
+    assert_eq!(payload.remove("54").as_deref(), Some("1000"));
+    assert_eq!(payload.remove("62.01").as_deref(), Some("INV-1"));
+    assert_eq!(payload.remove("62.07").as_deref(), Some("T-12"));
+    assert_eq!(payload.get("62"), None);
+    assert_eq!(payload.to_string(), with_crc(BODY));
+}
+
+#[test]
+fn set_rejects_tag_63_and_bad_values() {
+    let mut payload = Payload::parse(&with_crc(BODY)).unwrap();
+    assert_eq!(
+        payload.set("63", "ABCD").unwrap_err().to_string(),
+        "Tag 63 is computed when the payload is written"
+    );
+    assert_eq!(
+        payload.set("62.07", "").unwrap_err().to_string(),
+        "Value must not be empty; use remove instead"
+    );
+    assert_eq!(
+        payload.set("15.01", "X").unwrap_err().to_string(),
+        "Tag 15 is not a template"
+    );
+    assert_eq!(
+        payload.set("62.7", "X").unwrap_err().to_string(),
+        "Invalid tag path '62.7'"
+    );
+    assert_eq!(payload.to_string(), with_crc(BODY));
+}
+
+#[test]
+fn normalize_reorders_tags_and_recomputes_the_crc() {
+    let shuffled = with_crc("00020101021129110007abc@dev5802KH53031165902Ex6010Phnom Penh");
+    assert_eq!(normalize(&shuffled).unwrap(), with_crc(BODY));
+
+    let sub_tags_shuffled = with_crc(&format!("{}62130704T-120101X", BODY));
+    assert_eq!(
+        normalize(&sub_tags_shuffled).unwrap(),
+        with_crc(&format!("{}62130101X0704T-12", BODY))
+    );
+}
+
+#[test]
+fn parse_checks_the_crc_when_present() {
+    let err = Payload::parse(&format!("{}63040000", BODY)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert_eq!(err.to_string(), "CRC does not match the payload");
+
+    // Without tag 63 there is nothing to check; writing adds it.
+    assert_eq!(Payload::parse(BODY).unwrap().to_string(), with_crc(BODY));
+}
+
+#[test]
+fn parse_rejects_malformed_templates() {
+    for (body, message) in [
+        (
+            "00020101021129110007abc@dev53031165802KH5902Ex6010Phnom Penh6203XYZ",
+            "Malformed template 62: Truncated TLV 'XYZ'",
+        ),
+        (
+            "00020101021126040099",
+            "Malformed template 26: Declared length of tag 00 exceeds remaining slice",
+        ),
+        (
+            "00020101021129110007abc@dev8104ab12",
+            "Malformed template 81: Invalid tag code 'ab'",
+        ),
+    ] {
+        let err = Payload::parse(&with_crc(body)).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData, "{}", body);
+        assert_eq!(err.to_string(), message, "{}", body);
+    }
+
+    let err = Payload::parse(&with_crc(&format!("{}62100101X0101Y", BODY))).unwrap_err();
+    assert_eq!(err.to_string(), "Duplicate sub-tag 62.01");
+}
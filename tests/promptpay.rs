@@ -0,0 +1,109 @@
+mod common;
+
+use common::with_crc;
+use khqr_sdk::{PromptPay, PromptPayAccount, TransactionAmount};
+
+/// Public PromptPay examples, which put tag 58 before tag 53.
+const MOBILE_STATIC: &str =
+    "00020101021129370016A000000677010111011300668012345675802TH530376463046197";
+const NATIONAL_ID_STATIC: &str =
+    "00020101021129370016A000000677010111021311111111111115802TH530376463047B5A";
+
+fn mobile(amount: Option<f32>) -> PromptPay {
+    PromptPay {
+        account: PromptPayAccount::mobile("080-123-4567").unwrap(),
+        dynamic: amount.is_some(),
+        amount: amount.map(TransactionAmount::THB),
+        merchant_name: None,
+        merchant_city: None,
+    }
+}
+
+#[test]
+fn decodes_public_vectors() {
+    let promptpay = PromptPay::decode_qr(MOBILE_STATIC).unwrap();
+    assert_eq!(promptpay, mobile(None));
+    assert!(promptpay.is_static());
+
+    let promptpay = PromptPay::decode_qr(NATIONAL_ID_STATIC).unwrap();
+    assert_eq!(
+        promptpay.account,
+        PromptPayAccount::NationalId("1111111111111".to_string())
+    );
+    assert!(promptpay.is_static());
+}
+
+#[test]
+fn regenerates_public_vectors_in_canonical_order() {
+    let promptpay = PromptPay::decode_qr(MOBILE_STATIC).unwrap();
+    assert_eq!(
+        promptpay.generate_qr().unwrap(),
+        "00020101021129370016A0000006770101110113006680123456753037645802TH6304BE2B"
+    );
+}
+
+#[test]
+fn round_trips_an_amount() {
+    let promptpay = mobile(Some(4.22));
+    let qr = promptpay.generate_qr().unwrap();
+    assert_eq!(
+        qr,
+        with_crc("00020101021229370016A00000067701011101130066801234567530376454044.225802TH")
+    );
+    assert_eq!(PromptPay::decode_qr(&qr).unwrap(), promptpay);
+}
+
+#[test]
+fn keeps_a_dynamic_payload_without_an_amount_dynamic() {
+    let promptpay = PromptPay {
+        dynamic: true,
+        ..mobile(None)
+    };
+    let qr = promptpay.generate_qr().unwrap();
+    assert!(qr.starts_with("000201010212"));
+
+    let decoded = PromptPay::decode_qr(&qr).unwrap();
+    assert!(decoded.dynamic);
+    assert!(!decoded.is_static());
+    assert_eq!(decoded.generate_qr().unwrap(), qr);
+}
+
+#[test]
+fn round_trips_a_bill_payment() {
+    let promptpay = PromptPay {
+        account: PromptPayAccount::BillPayment {
+            biller_id: "010554612345601".to_string(),
+            reference_1: "INV001".to_string(),
+            reference_2: Some("CUST9".to_string()),
+        },
+        dynamic: true,
+        amount: Some(TransactionAmount::THB(1250.5)),
+        merchant_name: Some("Example".to_string()),
+        merchant_city: Some("Bangkok".to_string()),
+    };
+    let qr = promptpay.generate_qr().unwrap();
+    assert_eq!(PromptPay::decode_qr(&qr).unwrap(), promptpay);
+}
+
+#[test]
+fn rejects_amounts_in_other_currencies() {
+    let promptpay = PromptPay {
+        amount: Some(TransactionAmount::USD(1.0)),
+        ..mobile(None)
+    };
+    let err = promptpay.generate_qr().unwrap_err();
+    assert_eq!(err.to_string(), "PromptPay amount must be in THB, got USD");
+}
+
+#[test]
+fn rejects_an_unknown_point_of_initiation_method() {
+    for method in ["13", "xx"] {
+        let body = MOBILE_STATIC.replacen("010211", &format!("0102{}", method), 1);
+        let qr = with_crc(&body[..body.len() - 8]);
+        let err = PromptPay::decode_qr(&qr).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("Invalid point of initiation method '{}' (tag 01)", method)
+        );
+    }
+}
@@ -0,0 +1,94 @@
+import pytest
+
+import khqr_sdk
+
+STATIC_KHR = "00020101021129110007abc@dev53031165802KH5902Ex6010Phnom Penh63042C8E"
+DYNAMIC_USD = (
+    "00020101021229110007abc@dev530384054041.505802KH5902Ex6010Phnom Penh"
+    "62090105INV-163040E45"
+)
+
+
+def individual(account="abc@dev"):
+    return khqr_sdk.MerchantType.Indivual(khqr_sdk.IndivualInformation(account))
+
+
+def dynamic_bakong(account="abc@dev"):
+    return khqr_sdk.Bakong(
+        khqr_sdk.PointOfInitialMethod.Dynamic(
+            additional_data_field=None,
+            amount=khqr_sdk.TransactionAmount.USD(1.5),
+        ),
+        individual(account),
+        "Ex",
+        merchant_city=khqr_sdk.MerchantCity.PhnomPenh,
+        country_code=khqr_sdk.CountryCode.KH,
+        additional_data_template=khqr_sdk.AdditionalDataTemplate(bill_number="INV-1"),
+    )
+
+
+def test_decode_golden_static():
+    bakong = khqr_sdk.decode(STATIC_KHR)
+
+    assert bakong.qr_type == khqr_sdk.PointOfInitialMethod.Static(khqr_sdk.TransactionCurrency.KHR)
+    assert bakong.merchant_type == individual()
+    assert bakong.merchant_name == "Ex"
+    assert bakong.merchant_city == khqr_sdk.MerchantCity.PhnomPenh
+    assert bakong.country_code == khqr_sdk.CountryCode.KH
+    assert bakong.generate() == STATIC_KHR
+
+
+def test_generate_golden_dynamic():
+    assert khqr_sdk.generate(dynamic_bakong()) == DYNAMIC_USD
+    assert dynamic_bakong().generate() == DYNAMIC_USD
+
+
+def test_round_trip_after_edit():
+    bakong = khqr_sdk.Bakong.decode(DYNAMIC_USD)
+    assert bakong == dynamic_bakong()
+
+    bakong.merchant_name = "Other"
+    decoded = khqr_sdk.decode(bakong.generate())
+
+    assert decoded.merchant_name == "Other"
+    assert decoded.additional_data_template.bill_number == "INV-1"
+
+
+def test_hash_and_crc_vectors():
+    assert khqr_sdk.verify_crc(STATIC_KHR)
+    assert not khqr_sdk.verify_crc(STATIC_KHR[:-4] + "0000")
+    assert khqr_sdk.md5_hash(STATIC_KHR) == "6aefb648429430cabe19df948c6ea1dc"
+    assert khqr_sdk.short_hash(STATIC_KHR) == "6aefb648"
+
+
+def test_validate_reports_findings():
+    assert khqr_sdk.validate(STATIC_KHR) == []
+
+    [finding] = khqr_sdk.validate(STATIC_KHR[:-4] + "0000")
+    assert finding.severity == khqr_sdk.Severity.Error
+    assert finding.path == "63"
+    assert finding.message == "CRC is missing or does not match the payload"
+
+
+@pytest.mark.parametrize(
+    "payload, message",
+    [
+        (STATIC_KHR[:-4] + "0000", "CRC is missing or does not match the payload"),
+        ("000201", "Missing currency for static QR"),
+        ("", "Payload must start with the payload format indicator (tag 00)"),
+    ],
+)
+def test_decode_errors_raise_value_error(payload, message):
+    with pytest.raises(ValueError) as error:
+        khqr_sdk.decode(payload)
+    assert str(error.value) == message
+
+
+def test_generate_errors_raise_value_error():
+    with pytest.raises(ValueError, match="exactly one '@'"):
+        dynamic_bakong(account="abc").generate()
+
+
+def test_constructor_errors_raise_value_error():
+    with pytest.raises(ValueError):
+        khqr_sdk.MerchantLocation.from_postal_code("not a postal code")
@@ -0,0 +1,113 @@
+mod common;
+
+use common::with_crc;
+use khqr_sdk::{AdditionalDataTemplate, Qris, QrisAccount, QrisMerchantCriteria};
+
+/// A static micro-merchant payload laid out like Bank Indonesia's samples: a DANA issuer
+/// template in tag 26, the QRIS repository template in tag 51 and the postal code in tag 61.
+const MERCHANT_STATIC: &str = "00020101021126570011ID.DANA.WWW011893600915302259148102090225914810303UMI51440014ID.CO.QRIS.WWW0215ID10200176114730303UMI5204581253033605802ID5912Warung Sayur6010Kab. Demak6105595676304E276";
+
+fn qris(amount: Option<u64>) -> Qris {
+    Qris {
+        accounts: vec![QrisAccount {
+            tag: 26,
+            guid: "ID.DANA.WWW".to_string(),
+            merchant_pan: Some("936009153022591481".to_string()),
+            merchant_id: Some("022591481".to_string()),
+            criteria: Some(QrisMerchantCriteria::Micro),
+        }],
+        nmid: "ID1020017611473".to_string(),
+        criteria: QrisMerchantCriteria::Micro,
+        merchant_category_code: "5812".to_string(),
+        dynamic: amount.is_some(),
+        amount,
+        merchant_name: "Warung Sayur".to_string(),
+        merchant_city: "Kab. Demak".to_string(),
+        postal_code: "59567".to_string(),
+        additional_data_template: None,
+    }
+}
+
+#[test]
+fn decodes_a_merchant_payload() {
+    let decoded = Qris::decode_qr(MERCHANT_STATIC).unwrap();
+    assert_eq!(decoded, qris(None));
+    assert!(decoded.is_static());
+    assert_eq!(decoded.generate_qr().unwrap(), MERCHANT_STATIC);
+}
+
+#[test]
+fn round_trips_static_and_dynamic_payloads() {
+    let with_bill = Qris {
+        additional_data_template: Some(AdditionalDataTemplate {
+            bill_number: Some("INV-1".to_string()),
+            store_label: None,
+            terminal_label: None,
+            mobile_number: None,
+            purpose_of_transaction: None,
+        }),
+        ..qris(Some(15_000))
+    };
+    for qris in [qris(None), qris(Some(15_000)), with_bill] {
+        let qr = qris.generate_qr().unwrap();
+        assert_eq!(Qris::decode_qr(&qr).unwrap(), qris);
+    }
+}
+
+#[test]
+fn writes_issuer_templates_in_tag_order() {
+    let mut qris = qris(None);
+    qris.accounts.insert(
+        0,
+        QrisAccount {
+            tag: 27,
+            guid: "ID.CO.BANKMANDIRI.WWW".to_string(),
+            merchant_pan: None,
+            merchant_id: None,
+            criteria: None,
+        },
+    );
+    let qr = qris.generate_qr().unwrap();
+    assert!(
+        qr.find("2657").unwrap() < qr.find("2725").unwrap(),
+        "{}",
+        qr
+    );
+
+    qris.accounts.sort_by_key(|account| account.tag);
+    assert_eq!(Qris::decode_qr(&qr).unwrap(), qris);
+}
+
+#[test]
+fn accepts_whole_rupiah_written_with_decimals() {
+    let body = MERCHANT_STATIC
+        .replacen("010211", "010212", 1)
+        .replace("5802ID", "540815000.005802ID");
+    let decoded = Qris::decode_qr(&with_crc(&body[..body.len() - 8])).unwrap();
+    assert_eq!(decoded, qris(Some(15_000)));
+
+    let body = body.replace("15000.00", "15000.50");
+    let err = Qris::decode_qr(&with_crc(&body[..body.len() - 8])).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "QRIS amount must be a whole number of rupiah"
+    );
+}
+
+#[test]
+fn rejects_a_missing_or_malformed_postal_code() {
+    let body = &MERCHANT_STATIC[..MERCHANT_STATIC.len() - 8];
+    let err = Qris::decode_qr(&with_crc(&body.replace("610559567", ""))).unwrap_err();
+    assert_eq!(err.to_string(), "Missing QRIS postal code (tag 61)");
+
+    let err = Qris::decode_qr(&with_crc(&body.replace("610559567", "61045956"))).unwrap_err();
+    assert_eq!(err.to_string(), "QRIS postal code must be exactly 5 digits");
+}
+
+#[test]
+fn rejects_a_missing_repository_template() {
+    let body = &MERCHANT_STATIC[..MERCHANT_STATIC.len() - 8];
+    let body = body.replace("51440014ID.CO.QRIS.WWW0215ID10200176114730303UMI", "");
+    let err = Qris::decode_qr(&with_crc(&body)).unwrap_err();
+    assert_eq!(err.to_string(), "Missing QRIS repository template (tag 51)");
+}
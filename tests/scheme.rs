@@ -0,0 +1,135 @@
+mod common;
+
+use chrono::{DateTime, TimeZone, Utc};
+use common::with_crc;
+use khqr_sdk::{Confidence, DecodedQr, MerchantType, Scheme, decode_any_at, detect_scheme};
+
+const PROMPTPAY: &str =
+    "00020101021129370016A000000677010111011300668012345675802TH530376463046197";
+const VIETQR: &str = "00020101021238570010A00000072701270006970403011300110123456780208\
+     QRIBFTTA530370454061800005802VN62340107NPS68690819thanh toan don hang63042E2E";
+const QRIS: &str = "00020101021126570011ID.DANA.WWW011893600915302259148102090225914810303UMI51440014ID.CO.QRIS.WWW0215ID10200176114730303UMI5204581253033605802ID5912Warung Sayur6010Kab. Demak6105595676304E276";
+const BAKONG_INDIVIDUAL: &str = "00020101021129110007abc@dev53031165802KH5902Ex6010Phnom Penh";
+const BAKONG_MERCHANT: &str =
+    "00020101021130260007abc@dev0103M010204ACLB53031165802KH5902Ex6010Phnom Penh";
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2020, 1, 1, 8, 0, 0).unwrap()
+}
+
+#[test]
+fn detects_each_scheme_with_high_confidence() {
+    for (qr, scheme) in [
+        (PROMPTPAY.to_string(), Scheme::PromptPay),
+        (VIETQR.to_string(), Scheme::VietQr),
+        (QRIS.to_string(), Scheme::Qris),
+        (with_crc(BAKONG_INDIVIDUAL), Scheme::BakongIndividual),
+        (with_crc(BAKONG_MERCHANT), Scheme::BakongMerchant),
+    ] {
+        let detection = detect_scheme(&qr);
+        assert_eq!(detection.scheme, scheme, "{:?}", detection.reasons);
+        assert_eq!(
+            detection.confidence,
+            Confidence::High,
+            "{:?}",
+            detection.reasons
+        );
+    }
+}
+
+#[test]
+fn decodes_with_the_detected_scheme() {
+    assert!(matches!(
+        decode_any_at(PROMPTPAY, now()),
+        Ok(DecodedQr::PromptPay(_))
+    ));
+    assert!(matches!(
+        decode_any_at(VIETQR, now()),
+        Ok(DecodedQr::VietQr(_))
+    ));
+    assert!(matches!(decode_any_at(QRIS, now()), Ok(DecodedQr::Qris(_))));
+    for qr in [BAKONG_INDIVIDUAL, BAKONG_MERCHANT] {
+        let qr = with_crc(qr);
+        let Ok(DecodedQr::Bakong(bakong)) = decode_any_at(&qr, now()) else {
+            panic!("expected a Bakong payload: {}", qr);
+        };
+        assert_eq!(bakong.generate_qr().unwrap(), qr);
+    }
+}
+
+#[test]
+fn falls_back_to_bakong_for_an_unknown_payload() {
+    // A Bakong account ID without '@' identifies no scheme.
+    let qr = with_crc("00020101021129080004abcd53031165802KH5902Ex6010Phnom Penh");
+    let detection = detect_scheme(&qr);
+    assert_eq!(detection.scheme, Scheme::Unknown);
+    assert_eq!(detection.confidence, Confidence::Low);
+    assert_eq!(
+        detection.reasons,
+        vec!["no known account template or card network tag"]
+    );
+
+    let Ok(DecodedQr::Bakong(bakong)) = decode_any_at(&qr, now()) else {
+        panic!("expected the Bakong fallback to decode {}", qr);
+    };
+    let MerchantType::Indivual(information) = &bakong.merchant_type else {
+        panic!(
+            "expected an individual account, got {:?}",
+            bakong.merchant_type
+        );
+    };
+    assert_eq!(information.bakong_account_identifier, "abcd");
+}
+
+#[test]
+fn prefers_the_first_scheme_of_an_ambiguous_payload() {
+    // A NAPAS template and a Bakong account in one Cambodian payload.
+    let qr = with_crc(
+        "00020101021129110007abc@dev38400010A00000072701100006970403\
+         0208QRIBFTTA53031165802KH5902Ex6010Phnom Penh",
+    );
+    let detection = detect_scheme(&qr);
+    assert_eq!(detection.scheme, Scheme::VietQr);
+    assert_eq!(detection.confidence, Confidence::Medium);
+    assert_eq!(
+        detection.reasons,
+        vec![
+            "tag 38.00 is the NAPAS GUID A000000727",
+            "tag 29.00 is the Bakong account abc@dev",
+            "tag 58 is KH, expected VN",
+            "tag 53 is 116, expected 704",
+        ]
+    );
+    assert!(decode_any_at(&qr, now()).is_err());
+}
+
+#[test]
+fn lowers_confidence_for_a_bad_crc_or_payload_format() {
+    let wrong_crc = format!("{}63040000", BAKONG_INDIVIDUAL);
+    let detection = detect_scheme(&wrong_crc);
+    assert_eq!(detection.scheme, Scheme::BakongIndividual);
+    assert_eq!(detection.confidence, Confidence::Medium);
+    assert!(
+        detection
+            .reasons
+            .contains(&"CRC does not match the payload".to_string())
+    );
+
+    let detection = detect_scheme(&with_crc(
+        &BAKONG_INDIVIDUAL.replacen("000201", "000202", 1),
+    ));
+    assert_eq!(detection.scheme, Scheme::BakongIndividual);
+    assert_eq!(detection.confidence, Confidence::Low);
+
+    let detection = detect_scheme("not a payload");
+    assert_eq!(detection.scheme, Scheme::Unknown);
+    assert_eq!(detection.confidence, Confidence::Low);
+}
+
+#[test]
+fn detects_a_card_network_only_payload_with_medium_confidence() {
+    let qr = with_crc("0002010102110216411111111111111153038405802US5902Ex6008New York");
+    let detection = detect_scheme(&qr);
+    assert_eq!(detection.scheme, Scheme::Visa);
+    assert_eq!(detection.confidence, Confidence::Medium);
+}
@@ -0,0 +1,140 @@
+#![cfg(feature = "server")]
+
+mod common;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use common::with_crc;
+use http_body_util::BodyExt;
+use khqr_sdk::{Bakong, MerchantConfig, md5_hash, router};
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+const CONFIG: &str = r#"
+[[stores]]
+id = "bkk-1"
+merchant_name = "Example"
+merchant_city = "Phnom Penh"
+terminals = ["T-01"]
+
+[stores.account]
+type = "individual"
+bakong_account_identifier = "abc@dev"
+"#;
+
+const STATIC_KHR: &str = "00020101021129110007abc@dev53031165802KH5902Ex6010Phnom Penh63042C8E";
+
+async fn post(path: &str, body: impl Into<Body>) -> (StatusCode, Value) {
+    let request = Request::post(path)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(body.into())
+        .unwrap();
+    let response = router(MerchantConfig::from_toml_str(CONFIG).unwrap())
+        .oneshot(request)
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+async fn post_json(path: &str, body: Value) -> (StatusCode, Value) {
+    post(path, body.to_string()).await
+}
+
+#[tokio::test]
+async fn generates_a_payload_for_a_store() {
+    let (status, body) = post_json(
+        "/qr",
+        json!({ "store_id": "bkk-1", "terminal": "T-01", "amount": { "USD": 1.5 }, "bill_number": "INV-1" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let payload = body["payload"].as_str().unwrap();
+    assert_eq!(body["md5"], md5_hash(payload));
+    let bakong = Bakong::decode_qr(payload).unwrap();
+    assert_eq!(bakong.merchant_name, "Example");
+    let template = bakong.additional_data_template.unwrap();
+    assert_eq!(template.bill_number.as_deref(), Some("INV-1"));
+    assert_eq!(template.terminal_label.as_deref(), Some("T-01"));
+}
+
+#[tokio::test]
+async fn generate_maps_errors_to_statuses() {
+    let (status, body) = post_json("/qr", json!({ "store_id": "nope" })).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["error"]["kind"], "NotFound");
+
+    let (status, body) = post_json(
+        "/qr",
+        json!({ "store_id": "bkk-1", "expires_in_seconds": 60 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["kind"], "InvalidInput");
+    assert_eq!(
+        body["error"]["message"],
+        "expires_in_seconds requires an amount"
+    );
+
+    let (status, body) = post_json(
+        "/qr",
+        json!({ "store_id": "bkk-1", "image": "png", "size": 4_000_000_000u32 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["kind"], "InvalidInput");
+    assert_eq!(
+        body["error"]["message"],
+        "Image size must be at most 2048 pixels, got 4000000000"
+    );
+
+    let (status, body) = post("/qr", "{").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["kind"], "InvalidInput");
+}
+
+#[tokio::test]
+async fn decodes_a_payload() {
+    let (status, body) = post_json("/decode", json!({ "qr": STATIC_KHR })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["merchant_name"], "Ex");
+    assert_eq!(body["qr_type"], json!({ "Static": "KHR" }));
+}
+
+#[tokio::test]
+async fn decode_reports_invalid_data_as_unprocessable() {
+    let wrong_crc = STATIC_KHR.replace("2C8E", "0000");
+    let (status, body) = post_json("/decode", json!({ "qr": wrong_crc })).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["error"]["kind"], "InvalidData");
+    assert_eq!(
+        body["error"]["message"],
+        "CRC is missing or does not match the payload"
+    );
+}
+
+#[tokio::test]
+async fn warnings_alone_leave_a_payload_valid() {
+    // Tag 15 after tag 53 is legacy ordering: reported, but not an error.
+    let legacy_order =
+        with_crc("00020101021229110007abc@dev530311615041234540412345802KH5902Ex6010Phnom Penh");
+    let (status, body) = post_json("/validate", json!({ "qr": legacy_order })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["valid"], true);
+    assert_eq!(body["findings"][0]["severity"], "Warning");
+    assert_eq!(body["findings"][0]["path"], "15");
+
+    let (_, body) = post_json("/validate", json!({ "qr": STATIC_KHR })).await;
+    assert_eq!(body, json!({ "valid": true, "findings": [] }));
+}
+
+#[tokio::test]
+async fn errors_make_a_payload_invalid() {
+    let wrong_crc = STATIC_KHR.replace("2C8E", "0000");
+    let (status, body) = post_json("/validate", json!({ "qr": wrong_crc })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["valid"], false);
+    assert_eq!(body["findings"][0]["severity"], "Error");
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use chrono::{Duration, Utc};
+use common::with_crc;
+use khqr_sdk::{
+    AdditionalDataField, Bakong, PointOfInitialMethod, TransactionAmount, TransactionCurrency,
+};
+
+fn static_khr() -> (Bakong, String) {
+    let qr = with_crc("00020101021129110007abc@dev53031165802KH5902Ex6010Phnom Penh");
+    (Bakong::decode_qr(&qr).unwrap(), qr)
+}
+
+fn expiry() -> AdditionalDataField {
+    AdditionalDataField::new(Utc::now() + Duration::minutes(10)).unwrap()
+}
+
+#[test]
+fn to_dynamic_writes_tag_01_as_12_with_the_amount() {
+    let (bakong, _) = static_khr();
+    let dynamic = bakong
+        .to_dynamic(
+            TransactionAmount::KHR(1000),
+            Some("INV-1".to_string()),
+            Some(expiry()),
+        )
+        .unwrap();
+
+    let qr = dynamic.generate_qr().unwrap();
+    assert!(qr.starts_with("000201010212"), "{}", qr);
+    assert!(qr.contains("54041000"), "{}", qr);
+    assert!(qr.contains("62090105INV-1"), "{}", qr);
+    assert!(qr.contains("99340013"), "{}", qr);
+    assert_eq!(Bakong::decode_qr(&qr).unwrap().generate_qr().unwrap(), qr);
+}
+
+#[test]
+fn to_static_drops_the_amount_expiry_and_bill_number() {
+    let (bakong, qr) = static_khr();
+    let dynamic = bakong
+        .to_dynamic(
+            TransactionAmount::KHR(1000),
+            Some("INV-1".to_string()),
+            Some(expiry()),
+        )
+        .unwrap();
+
+    let back = dynamic.to_static();
+    assert_eq!(
+        back.qr_type,
+        PointOfInitialMethod::Static(TransactionCurrency::KHR)
+    );
+    assert_eq!(back.additional_data_template, None);
+    assert_eq!(back.generate_qr().unwrap(), qr);
+    assert_eq!(back.to_static(), back);
+}
+
+#[test]
+fn to_dynamic_rejects_another_currency_and_dynamic_qrs() {
+    let (bakong, _) = static_khr();
+    let err = bakong
+        .to_dynamic(TransactionAmount::USD(1.5), None, None)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Amount currency USD does not match the static QR currency KHR"
+    );
+
+    let dynamic = bakong
+        .to_dynamic(TransactionAmount::KHR(1000), None, None)
+        .unwrap();
+    let err = dynamic
+        .to_dynamic(TransactionAmount::KHR(2000), None, None)
+        .unwrap_err();
+    assert_eq!(err.to_string(), "QR is already dynamic");
+}
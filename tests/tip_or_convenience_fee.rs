@@ -0,0 +1,95 @@
+mod common;
+
+use common::with_crc;
+use khqr_sdk::{Bakong, TipOrConvenienceFee, TransactionAmount};
+
+/// A static KHR QR with `tags` between tag 53 and tag 58.
+fn qr(tags: &str) -> String {
+    with_crc(&format!(
+        "00020101021129110007abc@dev5303116{}5802KH5902Ex6010Phnom Penh",
+        tags
+    ))
+}
+
+fn decode_err(tags: &str) -> String {
+    Bakong::decode_qr(&qr(tags)).unwrap_err().to_string()
+}
+
+#[test]
+fn round_trips_each_indicator() {
+    for (tags, fee) in [
+        ("550201", TipOrConvenienceFee::prompt_for_tip()),
+        (
+            "5502025603500",
+            TipOrConvenienceFee::fixed(TransactionAmount::KHR(500)),
+        ),
+        ("55020357043.50", TipOrConvenienceFee::percentage(3.5)),
+    ] {
+        let qr = qr(tags);
+        let bakong = Bakong::decode_qr(&qr).unwrap();
+        assert_eq!(bakong.tip_or_convenience_fee, Some(fee), "{}", tags);
+        assert_eq!(bakong.generate_qr().unwrap(), qr);
+    }
+}
+
+#[test]
+fn rejects_a_tip_prompt_with_a_fee() {
+    assert_eq!(
+        decode_err("5502015603500"),
+        "Tip prompt (tag 55 = 01) must not carry tag 56 or 57"
+    );
+}
+
+#[test]
+fn rejects_a_missing_or_zero_fixed_fee() {
+    assert_eq!(
+        decode_err("550202"),
+        "Fixed convenience fee (tag 55 = 02) requires tag 56 and not tag 57"
+    );
+    assert_eq!(
+        decode_err("55020256010"),
+        "Convenience fee must be positive"
+    );
+}
+
+#[test]
+fn rejects_a_missing_or_out_of_range_percentage() {
+    assert_eq!(
+        decode_err("5502035703100"),
+        "Convenience fee percentage must be 0.01 to 99.99, got 100"
+    );
+    assert_eq!(
+        decode_err("550203"),
+        "Percentage convenience fee (tag 55 = 03) requires tag 57 and not tag 56"
+    );
+}
+
+#[test]
+fn rejects_both_fee_tags() {
+    for (indicator, message) in [
+        ("01", "Tip prompt (tag 55 = 01) must not carry tag 56 or 57"),
+        (
+            "02",
+            "Fixed convenience fee (tag 55 = 02) requires tag 56 and not tag 57",
+        ),
+        (
+            "03",
+            "Percentage convenience fee (tag 55 = 03) requires tag 57 and not tag 56",
+        ),
+    ] {
+        let tags = format!("5502{}560350057043.50", indicator);
+        assert_eq!(decode_err(&tags), message, "{}", tags);
+    }
+}
+
+#[test]
+fn rejects_fee_tags_without_an_indicator() {
+    assert_eq!(
+        decode_err("5603500"),
+        "Convenience fee (tag 56 or 57) requires tag 55"
+    );
+    assert_eq!(
+        decode_err("550204"),
+        "Unknown tip or convenience indicator '04'"
+    );
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use common::with_crc;
+use khqr_sdk::{Bakong, UnionPayMerchant};
+
+const IIN: &str = "12345678";
+
+fn account(merchant_id: &str) -> UnionPayMerchant {
+    UnionPayMerchant::Account {
+        acquirer_iin: IIN.to_string(),
+        merchant_id: merchant_id.to_string(),
+    }
+}
+
+#[test]
+fn reads_an_iin_followed_by_a_merchant_id() {
+    for merchant_id in ["M", "MERCHANT0000001"] {
+        assert_eq!(
+            UnionPayMerchant::from_string(&format!("{}{}", IIN, merchant_id)),
+            account(merchant_id)
+        );
+    }
+}
+
+#[test]
+fn keeps_other_values_raw() {
+    for value in [
+        // No merchant ID.
+        IIN.to_string(),
+        // A 16-character merchant ID.
+        format!("{}MERCHANT00000001", IIN),
+        // A 7-digit IIN.
+        "1234567AMERCHANT".to_string(),
+        // A merchant ID with punctuation.
+        format!("{}MER-01", IIN),
+        "UP-1".to_string(),
+    ] {
+        assert_eq!(
+            UnionPayMerchant::from_string(&value),
+            UnionPayMerchant::Raw(value.clone())
+        );
+    }
+}
+
+#[test]
+fn validates_the_account_layout() {
+    assert_eq!(
+        UnionPayMerchant::new(IIN.to_string(), "MERCHANT0000001".to_string()).unwrap(),
+        account("MERCHANT0000001")
+    );
+
+    for iin in ["1234567", "123456789", "1234567A"] {
+        let err = UnionPayMerchant::new(iin.to_string(), "M".to_string()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "UnionPay acquirer IIN must be exactly 8 digits"
+        );
+    }
+    for merchant_id in ["", "MERCHANT00000001", "MER-01"] {
+        let err = UnionPayMerchant::new(IIN.to_string(), merchant_id.to_string()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "UnionPay merchant ID must be 1 to 15 letters or digits"
+        );
+    }
+}
+
+#[test]
+fn round_trips_tag_15_through_bakong() {
+    for (tag_15, merchant) in [
+        ("1523", account("MERCHANT0000001")),
+        ("1504", UnionPayMerchant::Raw("UP-1".to_string())),
+    ] {
+        let value = match &merchant {
+            UnionPayMerchant::Account {
+                acquirer_iin,
+                merchant_id,
+            } => format!("{}{}", acquirer_iin, merchant_id),
+            UnionPayMerchant::Raw(value) => value.clone(),
+        };
+        let qr = with_crc(&format!(
+            "000201010211{}{}29110007abc@dev53031165802KH5902Ex6010Phnom Penh",
+            tag_15, value
+        ));
+        let bakong = Bakong::decode_qr(&qr).unwrap();
+        assert_eq!(bakong.unionpay_merchant, Some(merchant));
+        assert_eq!(bakong.generate_qr().unwrap(), qr);
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serializes_accounts_as_objects_and_raw_values_as_strings() {
+    let merchant = account("MERCHANT0000001");
+    let json = serde_json::to_value(&merchant).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({"acquirer_iin": IIN, "merchant_id": "MERCHANT0000001"})
+    );
+    assert_eq!(
+        serde_json::from_value::<UnionPayMerchant>(json).unwrap(),
+        merchant
+    );
+
+    let raw = UnionPayMerchant::Raw("UP-1".to_string());
+    let json = serde_json::to_value(&raw).unwrap();
+    assert_eq!(json, serde_json::json!("UP-1"));
+    assert_eq!(
+        serde_json::from_value::<UnionPayMerchant>(json).unwrap(),
+        raw
+    );
+}
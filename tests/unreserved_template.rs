@@ -0,0 +1,111 @@
+mod common;
+
+use common::with_crc;
+use khqr_sdk::{Bakong, GuidRegistry, UnreservedField, UnreservedTemplate};
+
+const STATIC_KHR: &str = "00020101021129110007abc@dev53031165802KH5902Ex6010Phnom Penh";
+
+fn field(tag: u8, value: &str) -> UnreservedField {
+    UnreservedField {
+        tag,
+        value: value.to_string(),
+    }
+}
+
+#[test]
+fn looks_up_templates_by_guid() {
+    let qr = with_crc(&format!(
+        "{}80370019COM.EXAMPLE.LOYALTY0110CAMPAIGN-781180008A00000010202M1",
+        STATIC_KHR
+    ));
+    let bakong = Bakong::decode_qr(&qr).unwrap();
+
+    let loyalty = bakong.unreserved_template("COM.EXAMPLE.LOYALTY").unwrap();
+    assert_eq!(loyalty.tag, 80);
+    assert_eq!(loyalty.field(1), Some("CAMPAIGN-7"));
+    assert_eq!(loyalty.field(2), None);
+    assert_eq!(
+        bakong.unreserved_template("com.example.loyalty"),
+        Some(loyalty)
+    );
+
+    let other = bakong.unreserved_template("A0000001").unwrap();
+    assert_eq!(other.tag, 81);
+    assert_eq!(other.fields, vec![field(2, "M1")]);
+
+    assert_eq!(bakong.unreserved_template("COM.EXAMPLE.OTHER"), None);
+    assert_eq!(bakong.generate_qr().unwrap(), qr);
+}
+
+#[test]
+fn writes_sub_tags_in_ascending_order() {
+    let mut bakong = Bakong::decode_qr(&with_crc(STATIC_KHR)).unwrap();
+    bakong.unreserved_templates.push(
+        UnreservedTemplate::new(
+            80,
+            "COM.EXAMPLE.LOYALTY".to_string(),
+            vec![field(2, "B"), field(1, "A")],
+        )
+        .unwrap(),
+    );
+    assert_eq!(
+        bakong.generate_qr().unwrap(),
+        with_crc(&format!(
+            "{}80330019COM.EXAMPLE.LOYALTY0101A0201B",
+            STATIC_KHR
+        ))
+    );
+}
+
+#[test]
+fn rejects_invalid_templates() {
+    for (result, message) in [
+        (
+            UnreservedTemplate::new(99, "A0000001".to_string(), Vec::new()),
+            "Tag 99 is not an unreserved template (80-98)",
+        ),
+        (
+            UnreservedTemplate::new(80, String::new(), Vec::new()),
+            "GUID in tag 80 must be 1 to 32 printable characters",
+        ),
+        (
+            UnreservedTemplate::new(80, "A0000001".to_string(), vec![field(0, "X")]),
+            "Sub-tag 80.00 must be 01 to 99",
+        ),
+        (
+            UnreservedTemplate::new(
+                80,
+                "A0000001".to_string(),
+                vec![field(1, "X"), field(1, "Y")],
+            ),
+            "Duplicate sub-tag 80.01",
+        ),
+        (
+            UnreservedTemplate::from_string(80, "0101X"),
+            "Missing GUID (sub-tag 00) in tag 80",
+        ),
+    ] {
+        assert_eq!(result.unwrap_err().to_string(), message);
+    }
+}
+
+#[test]
+fn registers_guid_owners() {
+    let mut registry = GuidRegistry::default();
+    assert_eq!(registry.owner("COM.EXAMPLE.LOYALTY"), None);
+
+    registry.register("COM.EXAMPLE.LOYALTY", "Example Loyalty");
+    assert_eq!(
+        registry.owner("com.example.loyalty"),
+        Some("Example Loyalty")
+    );
+    registry.register("com.example.loyalty", "Example Rewards");
+    assert_eq!(
+        registry.owner("COM.EXAMPLE.LOYALTY"),
+        Some("Example Rewards")
+    );
+
+    let template =
+        UnreservedTemplate::new(80, "COM.EXAMPLE.LOYALTY".to_string(), Vec::new()).unwrap();
+    assert_eq!(registry.owner_of(&template), Some("Example Rewards"));
+}
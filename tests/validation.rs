@@ -0,0 +1,28 @@
+mod common;
+
+use common::with_crc;
+use khqr_sdk::{Bakong, Severity, validate};
+
+#[test]
+fn legacy_tag_order_is_only_a_warning() {
+    let qr =
+        with_crc("00020101021229110007abc@dev530311615041234540412345802KH5902Ex6010Phnom Penh");
+
+    let findings = validate(&qr);
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].severity, Severity::Warning);
+    assert_eq!(findings[0].path.as_deref(), Some("15"));
+    assert!(Bakong::decode_qr(&qr).is_ok());
+}
+
+#[test]
+fn a_broken_payload_is_an_error() {
+    let qr = "00020101021129110007abc@dev53031165802KH5902Ex6010Phnom Penh63040000";
+
+    let findings = validate(qr);
+    assert!(
+        findings
+            .iter()
+            .any(|finding| finding.severity == Severity::Error)
+    );
+}
@@ -0,0 +1,66 @@
+use khqr_sdk::{AdditionalDataTemplate, VietQr, VietQrService};
+
+/// The dynamic transfer example from the NAPAS VietQR specification: Sacombank (BIN 970403),
+/// 180,000 VND, bill number and purpose in tag 62.
+const NAPAS_DYNAMIC: &str = "00020101021238570010A00000072701270006970403011300110123456780208\
+     QRIBFTTA530370454061800005802VN62340107NPS68690819thanh toan don hang63042E2E";
+
+fn vietqr(amount: Option<u64>) -> VietQr {
+    VietQr {
+        bank_bin: "970436".to_string(),
+        account: "0011001234567".to_string(),
+        service: VietQrService::ToAccount,
+        dynamic: amount.is_some(),
+        amount,
+        merchant_name: None,
+        merchant_city: None,
+        additional_data_template: None,
+    }
+}
+
+#[test]
+fn round_trips_static_and_dynamic_payloads() {
+    for vietqr in [vietqr(None), vietqr(Some(50_000))] {
+        let qr = vietqr.generate_qr().unwrap();
+        assert_eq!(VietQr::decode_qr(&qr).unwrap(), vietqr);
+    }
+}
+
+#[test]
+fn keeps_a_dynamic_payload_without_an_amount_dynamic() {
+    let vietqr = VietQr {
+        dynamic: true,
+        ..vietqr(None)
+    };
+    let qr = vietqr.generate_qr().unwrap();
+    assert!(qr.starts_with("000201010212"));
+
+    let decoded = VietQr::decode_qr(&qr).unwrap();
+    assert_eq!(decoded, vietqr);
+    assert_eq!(decoded.generate_qr().unwrap(), qr);
+}
+
+#[test]
+fn decodes_the_napas_specification_vector() {
+    let vietqr = VietQr::decode_qr(NAPAS_DYNAMIC).unwrap();
+    assert_eq!(
+        vietqr,
+        VietQr {
+            bank_bin: "970403".to_string(),
+            account: "0011012345678".to_string(),
+            service: VietQrService::ToAccount,
+            dynamic: true,
+            amount: Some(180_000),
+            merchant_name: None,
+            merchant_city: None,
+            additional_data_template: Some(AdditionalDataTemplate {
+                bill_number: Some("NPS6869".to_string()),
+                store_label: None,
+                terminal_label: None,
+                mobile_number: None,
+                purpose_of_transaction: Some("thanh toan don hang".to_string()),
+            }),
+        }
+    );
+    assert_eq!(vietqr.generate_qr().unwrap(), NAPAS_DYNAMIC);
+}
@@ -0,0 +1,169 @@
+//! `write_qr` and `write_qr_to_slice` promise to encode without touching the heap.
+
+use chrono::{Duration, TimeZone, Utc};
+use khqr_sdk::{
+    AdditionalDataField, AdditionalDataTemplate, Bakong, Error, ExtensionTag, Extensions,
+    MerchantAccountTemplate, MerchantCity, MerchantInformation,
+    MerchantInformationLanguageTemplate, MerchantType, NetworkMerchantAccount,
+    PointOfInitialMethod, TagExtension, TipOrConvenienceFee, TransactionAmount,
+    TransactionCurrency, UnionPayMerchant, UnreservedField, UnreservedTemplate,
+};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations_during(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+macro_rules! extension {
+    ($name:ident, $tag:expr) => {
+        #[derive(Debug, Clone, PartialEq)]
+        struct $name(&'static str);
+
+        impl TagExtension for $name {
+            const TAG: ExtensionTag = $tag;
+
+            fn parse(_: &str) -> Result<Self, Error> {
+                unreachable!("only written in this test")
+            }
+
+            fn write_value<W: core::fmt::Write + ?Sized>(
+                &self,
+                writer: &mut W,
+            ) -> Result<(), Error> {
+                writer.write_str(self.0).map_err(Error::other)
+            }
+        }
+    };
+}
+
+extension!(ForeignAccount, ExtensionTag::Tag(27));
+extension!(LoyaltyMember, ExtensionTag::SubTag(62, 50));
+extension!(Campaign, ExtensionTag::SubTag(80, 5));
+extension!(WholeTemplate, ExtensionTag::Tag(81));
+extension!(Stamp, ExtensionTag::SubTag(99, 10));
+
+fn merchant() -> Bakong {
+    let mut extensions = Extensions::default();
+    extensions.insert(ForeignAccount("F-1")).unwrap();
+    extensions.insert(LoyaltyMember("M-1")).unwrap();
+    extensions.insert(Campaign("C9")).unwrap();
+    extensions.insert(WholeTemplate("W")).unwrap();
+
+    Bakong {
+        qr_type: PointOfInitialMethod::Static(TransactionCurrency::USD),
+        merchant_type: MerchantType::Merchant(MerchantInformation {
+            bakong_account_identifier: "abc@dev".to_string(),
+            merchant_identifier: "123456".to_string(),
+            acquiring_bank: "Dev Bank".to_string(),
+        }),
+        merchant_category_code: Some("5999".to_string()),
+        tip_or_convenience_fee: Some(TipOrConvenienceFee::fixed(TransactionAmount::USD(1.5))),
+        merchant_name: "Example".to_string(),
+        merchant_city: Some(MerchantCity::PhnomPenh),
+        postal_code: Some("120101".to_string()),
+        country_code: None,
+        additional_data_template: Some(AdditionalDataTemplate {
+            bill_number: Some("INV-1".to_string()),
+            store_label: Some("BKK1".to_string()),
+            terminal_label: None,
+            mobile_number: None,
+            purpose_of_transaction: None,
+        }),
+        unionpay_merchant: Some(UnionPayMerchant::Raw("UP-1".to_string())),
+        network_merchant_accounts: vec![
+            NetworkMerchantAccount::new(20, "E-1".to_string()).unwrap(),
+            NetworkMerchantAccount::new(2, "V-1".to_string()).unwrap(),
+        ],
+        merchant_account_templates: vec![
+            MerchantAccountTemplate::new(31, "0004ABCD".to_string()).unwrap(),
+        ],
+        merchant_information_language_template: Some(MerchantInformationLanguageTemplate {
+            language_preference: "km".to_string(),
+            merchant_name_alternate_language: "Example".to_string(),
+            merchant_city_alternate_language: "Phnom Penh".to_string(),
+        }),
+        unreserved_templates: vec![
+            UnreservedTemplate::new(
+                80,
+                "COM.EXAMPLE".to_string(),
+                vec![
+                    UnreservedField {
+                        tag: 7,
+                        value: "z".to_string(),
+                    },
+                    UnreservedField {
+                        tag: 2,
+                        value: "a".to_string(),
+                    },
+                ],
+            )
+            .unwrap(),
+        ],
+        extensions,
+    }
+}
+
+fn dynamic() -> Bakong {
+    let creation = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+    let mut bakong = merchant();
+    bakong.qr_type = PointOfInitialMethod::Dynamic {
+        additional_data_field: Some(
+            AdditionalDataField::with_creation(creation, creation + Duration::minutes(5)).unwrap(),
+        ),
+        amount: TransactionAmount::USD(12.5),
+    };
+    bakong.tip_or_convenience_fee = Some(TipOrConvenienceFee::percentage(2.5));
+    bakong.extensions.insert(Stamp("S")).unwrap();
+    bakong
+}
+
+#[test]
+fn write_qr_to_slice_does_not_allocate() {
+    for bakong in [merchant(), dynamic()] {
+        let expected = bakong.generate_qr().unwrap();
+        let mut buffer = [0u8; 512];
+        let mut written = "";
+        let allocations = allocations_during(|| {
+            written = bakong.write_qr_to_slice(&mut buffer).unwrap();
+        });
+        assert_eq!(allocations, 0, "{}", expected);
+        assert_eq!(written, expected);
+    }
+}
+
+#[test]
+fn write_qr_into_a_preallocated_string_does_not_allocate() {
+    let bakong = merchant();
+    let mut qr = String::with_capacity(512);
+    let allocations = allocations_during(|| bakong.write_qr(&mut qr).unwrap());
+    assert_eq!(allocations, 0);
+    assert_eq!(qr, bakong.generate_qr().unwrap());
+}